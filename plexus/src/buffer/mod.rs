@@ -93,12 +93,17 @@ use std::hash::Hash;
 use std::iter::FromIterator;
 use std::vec;
 use theon::adjunct::{FromItems, Map};
+use theon::ops::Cross;
+use theon::space::{EuclideanSpace, FiniteDimensional, Scalar, Vector};
+use theon::{AsPosition, Position};
 use thiserror::Error;
 use typenum::{self, NonZero, Unsigned as _, U3, U4};
 
 use crate::buffer::builder::BufferBuilder;
 use crate::builder::{Buildable, MeshBuilder};
 use crate::encoding::{FaceDecoder, FromEncoding, VertexDecoder};
+use crate::geometry::bvh::Bvh;
+use crate::geometry::tangent::{Tangent, TangentAccumulator};
 use crate::geometry::{FromGeometry, IntoGeometry};
 use crate::index::{
     BufferOf, Flat, Flat3, Flat4, FromIndexer, Grouping, HashIndexer, IndexBuffer, IndexOf,
@@ -354,6 +359,71 @@ where
     }
 }
 
+impl<N, G> MeshBuffer<Trigon<N>, G>
+where
+    N: Copy + Integer + NumCast + Unsigned,
+{
+    /// Computes a tangent for each vertex.
+    ///
+    /// The given function returns the position, normal, and texture
+    /// coordinates of a vertex. Tangents are computed in the manner of
+    /// MikkTSpace from the triangles that share each vertex. Unlike
+    /// MikkTSpace, vertices are never split, so triangles with mirrored
+    /// texture coordinates should not share vertices. The output is parallel
+    /// to the vertex buffer and includes `None` for vertices that are not
+    /// referenced by any triangle or for which no tangent can be computed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use nalgebra::{Point3, Vector3};
+    /// use plexus::buffer::MeshBuffer3;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::Trigon;
+    ///
+    /// let buffer = MeshBuffer3::<usize, (Point3<f64>, (f64, f64))>::from_raw_buffers(
+    ///     vec![Trigon::new(0, 1, 2)],
+    ///     vec![
+    ///         (Point3::new(0.0, 0.0, 0.0), (0.0, 0.0)),
+    ///         (Point3::new(1.0, 0.0, 0.0), (1.0, 0.0)),
+    ///         (Point3::new(0.0, 1.0, 0.0), (0.0, 1.0)),
+    ///     ],
+    /// )
+    /// .unwrap();
+    /// let tangents = buffer.tangents_with(|(position, uv)| (*position, Vector3::z(), *uv));
+    /// ```
+    pub fn tangents_with<S, F>(&self, f: F) -> Vec<Option<Tangent<Vector<S>>>>
+    where
+        S: EuclideanSpace,
+        Vector<S>: Cross<Output = Vector<S>>,
+        F: FnMut(&G) -> (S, Vector<S>, (Scalar<S>, Scalar<S>)),
+    {
+        let attributes = self.vertices.iter().map(f).collect::<Vec<_>>();
+        let mut accumulators = attributes
+            .iter()
+            .map(|(_, normal, _)| TangentAccumulator::new(*normal))
+            .collect::<Vec<_>>();
+        let index = |index: N| <usize as NumCast>::from(index).expect("index overflow");
+        for trigon in self.indices.iter() {
+            let [a, b, c] = trigon.0;
+            let (a, b, c) = (index(a), index(b), index(c));
+            for &(a, b, c) in &[(a, b, c), (b, c, a), (c, a, b)] {
+                accumulators[a].insert(
+                    [attributes[a].0, attributes[b].0, attributes[c].0],
+                    [attributes[a].2, attributes[b].2, attributes[c].2],
+                );
+            }
+        }
+        accumulators
+            .into_iter()
+            .map(|accumulator| accumulator.into_tangent())
+            .collect()
+    }
+}
+
 /// Exposes a [`MeshBuilder`] that can be used to construct a [`MeshBuffer`]
 /// incrementally from _surfaces_ and _facets_.
 ///
@@ -504,6 +574,35 @@ where
             .extend(buffer.indices.drain(..).map(|index| index.into() + offset));
         Ok(())
     }
+
+    /// Builds a bounding volume hierarchy over the polygons of the buffer.
+    ///
+    /// Polygons are identified by their index, which is the offset of their
+    /// first index in the index buffer divided by the arity of the buffer.
+    ///
+    /// See [`Bvh`].
+    ///
+    /// [`Bvh`]: crate::geometry::bvh::Bvh
+    pub fn bvh(&self) -> Bvh<usize, Position<G>>
+    where
+        G: AsPosition,
+        Position<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    {
+        let vertices = &self.vertices;
+        self.indices
+            .chunks(A::USIZE)
+            .enumerate()
+            .map(|(key, chunk)| {
+                (
+                    key,
+                    chunk.iter().map(move |index| {
+                        let index = <usize as NumCast>::from(*index).expect("index overflow");
+                        *vertices[index].as_position()
+                    }),
+                )
+            })
+            .collect()
+    }
 }
 
 impl<P, G> MeshBuffer<P, G>
@@ -541,6 +640,39 @@ where
         );
         Ok(())
     }
+
+    /// Builds a bounding volume hierarchy over the polygons of the buffer.
+    ///
+    /// Polygons are identified by their index in the index buffer.
+    ///
+    /// See [`Bvh`].
+    ///
+    /// [`Bvh`]: crate::geometry::bvh::Bvh
+    pub fn bvh(&self) -> Bvh<usize, Position<G>>
+    where
+        G: AsPosition,
+        Position<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        <P as Grouping>::Group: Clone + IntoVertices + Topological<Vertex = P::Vertex>,
+    {
+        let vertices = &self.vertices;
+        self.indices
+            .iter()
+            .enumerate()
+            .map(|(key, polygon)| {
+                (
+                    key,
+                    polygon
+                        .clone()
+                        .into_vertices()
+                        .into_iter()
+                        .map(move |index| {
+                            let index = <usize as NumCast>::from(index).expect("index overflow");
+                            *vertices[index].as_position()
+                        }),
+                )
+            })
+            .collect()
+    }
 }
 
 impl<P, Q, T, R, N, G> From<P> for MeshBuffer<R, G>
@@ -1144,16 +1276,16 @@ where
 #[cfg(test)]
 mod tests {
     use decorum::N64;
-    use nalgebra::Point3;
+    use nalgebra::{Point3, Vector3};
 
-    use crate::buffer::{MeshBuffer, MeshBuffer4, MeshBufferN};
+    use crate::buffer::{MeshBuffer, MeshBuffer3, MeshBuffer4, MeshBufferN};
     use crate::graph::MeshGraph;
     use crate::index::Flat3;
     use crate::prelude::*;
     use crate::primitive::cube::Cube;
     use crate::primitive::generate::Position;
     use crate::primitive::sphere::UvSphere;
-    use crate::primitive::{BoundedPolygon, UnboundedPolygon};
+    use crate::primitive::{BoundedPolygon, Trigon, UnboundedPolygon};
 
     type E3 = Point3<N64>;
 
@@ -1207,6 +1339,31 @@ mod tests {
         assert_eq!(13, buffer.as_vertex_slice().len());
     }
 
+    #[test]
+    fn tangents() {
+        let buffer = MeshBuffer3::<usize, (Point3<f64>, (f64, f64))>::from_raw_buffers(
+            vec![Trigon::new(0, 1, 2), Trigon::new(1, 3, 2)],
+            vec![
+                (Point3::new(0.0, 0.0, 0.0), (0.0, 0.0)),
+                (Point3::new(1.0, 0.0, 0.0), (1.0, 0.0)),
+                (Point3::new(0.0, 1.0, 0.0), (0.0, 1.0)),
+                (Point3::new(1.0, 1.0, 0.0), (1.0, 1.0)),
+                // Unreferenced.
+                (Point3::new(2.0, 2.0, 0.0), (2.0, 2.0)),
+            ],
+        )
+        .unwrap();
+        let tangents = buffer.tangents_with(|(position, uv)| (*position, Vector3::z(), *uv));
+
+        assert_eq!(5, tangents.len());
+        for tangent in &tangents[..4] {
+            let tangent = tangent.unwrap();
+            assert!((tangent.vector - Vector3::x()).norm() < 1e-9);
+            assert!((tangent.bitangent(Vector3::z()) - Vector3::y()).norm() < 1e-9);
+        }
+        assert!(tangents[4].is_none());
+    }
+
     #[test]
     fn convert_mesh_to_buffer_by_vertex() {
         let graph: MeshGraph<E3> = UvSphere::new(3, 2)
@@ -33,28 +33,68 @@ where
     F: Fn(T, T) -> Q,
 {
     let (storage, from) = from.unbind();
-    let capacity = if let Some(key) = to {
-        storage
-            .as_storage()
-            .contains_key(&key)
-            .ok_or(EntityError::EntityNotFound)?;
-        0
+    search_with(storage, Some(from), to, f, |_| Q::zero())
+}
+
+/// Searches for the least metrics from any of the given source entities.
+///
+/// Entities are visited in order of increasing metric plus heuristic, which
+/// is A* search. For Dijkstra's algorithm, the heuristic should return zero.
+/// The heuristic must never overestimate the metric to the nearest target
+/// (that is, it must be admissible and consistent). The search stops when any
+/// of the target entities is reached. If there are no targets, then all
+/// reachable entities are visited.
+///
+/// The metrics in the output tree are the metrics from the nearest source and
+/// do not include the heuristic.
+pub fn search_with<'a, M, T, Q, I, J, F, H>(
+    storage: &'a M,
+    from: I,
+    to: J,
+    f: F,
+    h: H,
+) -> Result<MetricTree<T::Key, Q>, EntityError>
+where
+    M: 'a + AsStorage<T::Entity>,
+    T: Adjacency + Bind<&'a M> + Copy + Unbind<&'a M>,
+    Q: Copy + Metric,
+    I: IntoIterator<Item = T::Key>,
+    J: IntoIterator<Item = T::Key>,
+    F: Fn(T, T) -> Q,
+    H: Fn(T) -> Q,
+{
+    let to = to
+        .into_iter()
+        .map(|key| {
+            storage
+                .as_storage()
+                .contains_key(&key)
+                .ok_or(EntityError::EntityNotFound)
+                .map(|_| key)
+        })
+        .collect::<Result<HashSet<_>, _>>()?;
+    let capacity = if to.is_empty() {
+        storage.as_storage().len()
     }
     else {
-        storage.as_storage().len()
+        0
     };
     let mut buffer = BinaryHeap::new();
     let mut breadcrumbs = HashSet::with_capacity(capacity);
     let mut metrics = HashMap::with_capacity(capacity);
 
-    metrics.insert(from, (None, Q::zero()));
-    buffer.push(KeyedMetric(from, Reverse(Q::zero())));
-    while let Some(KeyedMetric(key, Reverse(metric))) = buffer.pop() {
-        if Some(key) == to {
+    for key in from {
+        let entity = T::bind(storage, key).ok_or(EntityError::EntityNotFound)?;
+        metrics.insert(key, (None, Q::zero()));
+        buffer.push(KeyedMetric(key, Reverse(h(entity))));
+    }
+    while let Some(KeyedMetric(key, _)) = buffer.pop() {
+        if to.contains(&key) {
             break;
         }
         let entity = T::bind(storage, key).ok_or(EntityError::EntityNotFound)?;
         if breadcrumbs.insert(entity.key()) {
+            let (_, metric) = metrics[&key];
             for adjacent in entity
                 .adjacency()
                 .into_iter()
@@ -69,14 +109,17 @@ where
                 match metrics.entry(adjacent.key()) {
                     Entry::Occupied(entry) => {
                         if metric < entry.get().1 {
-                            entry.into_mut().1 = metric;
+                            *entry.into_mut() = (Some(entity.key()), metric);
+                        }
+                        else {
+                            continue;
                         }
                     }
                     Entry::Vacant(entry) => {
                         entry.insert((Some(entity.key()), metric));
                     }
                 }
-                buffer.push(KeyedMetric(adjacent.key(), Reverse(metric)));
+                buffer.push(KeyedMetric(adjacent.key(), Reverse(metric + h(adjacent))));
             }
         }
     }
@@ -90,7 +133,7 @@ mod tests {
     use theon::space::InnerSpace;
 
     use crate::entity::{dijkstra, EntityError};
-    use crate::graph::MeshGraph;
+    use crate::graph::{MeshGraph, VertexView};
     use crate::prelude::*;
     use crate::primitive::{Tetragon, Trigon};
 
@@ -158,4 +201,85 @@ mod tests {
         assert_eq!(cq, (Some(b), 4.0.into()));
         assert_eq!(dq, (Some(a), 2.0.into()));
     }
+
+    #[test]
+    fn update_predecessor_metrics() {
+        let graph = MeshGraph::<Point2<R64>>::from_raw_buffers(
+            vec![Trigon::new(0usize, 1, 2)],
+            vec![(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)],
+        )
+        .unwrap();
+        let find = |x: f64| {
+            graph
+                .vertices()
+                .find(|vertex| vertex.position().x == R64::from(x))
+                .unwrap()
+        };
+        // The fourth power of the distance along the arc from the source to
+        // the opposite vertex is greater than along the two other arcs.
+        let metrics = dijkstra::metrics_with(find(0.0), None, |from, to| {
+            let difference = to.position() - from.position();
+            let distance = (difference.x * difference.x) + (difference.y * difference.y);
+            distance * distance
+        })
+        .unwrap();
+        let b = find(1.0).key();
+        let c = find(2.0).key();
+
+        assert_eq!(*metrics.get(&c).unwrap(), (Some(b), 8.0.into()));
+    }
+
+    #[test]
+    fn multiple_source_metrics() {
+        let graph = MeshGraph::<()>::from_raw_buffers(
+            vec![Trigon::new(0usize, 1, 2), Trigon::new(2, 1, 3)],
+            vec![(); 4],
+        )
+        .unwrap();
+        let keys = graph
+            .vertices()
+            .filter(|vertex| vertex.valence() == 2)
+            .map(|vertex| vertex.key())
+            .collect::<Vec<_>>();
+        let metrics = dijkstra::search_with(
+            &graph,
+            keys.iter().cloned(),
+            None,
+            |_: VertexView<_>, _: VertexView<_>| 1usize,
+            |_| 0usize,
+        )
+        .unwrap();
+
+        assert_eq!(4, metrics.len());
+        assert!(metrics.values().all(|(_, metric)| *metric <= 1));
+    }
+
+    #[test]
+    fn target_metrics() {
+        let graph =
+            MeshGraph::<()>::from_raw_buffers(vec![Tetragon::new(0usize, 1, 2, 3)], vec![(); 4])
+                .unwrap();
+        let vertex = graph.vertices().nth(0).unwrap();
+        let b = vertex.outgoing_arc().destination_vertex().key();
+        let d = vertex
+            .outgoing_arc()
+            .opposite_arc()
+            .next_arc()
+            .destination_vertex()
+            .key();
+        let metrics = dijkstra::search_with(
+            &graph,
+            Some(vertex.key()),
+            vec![b, d],
+            |_: VertexView<_>, _: VertexView<_>| 1usize,
+            |_| 0usize,
+        )
+        .unwrap();
+
+        assert_eq!(*metrics.get(&b).unwrap(), (Some(vertex.key()), 1));
+        assert_eq!(*metrics.get(&d).unwrap(), (Some(vertex.key()), 1));
+        // The search stops at a target before the opposite vertex of the
+        // quadrilateral is reached.
+        assert_eq!(3, metrics.len());
+    }
 }
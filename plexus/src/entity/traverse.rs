@@ -67,71 +67,152 @@ pub trait Adjacency: ClosedView {
     fn adjacency(&self) -> Self::Output;
 }
 
+/// Adjacency filter.
+///
+/// Determines whether or not a traversal may move from one view to an adjacent
+/// view. Filters prune a traversal: if a filter rejects a move, then the
+/// adjacent view is not visited via that move (but may be visited via some
+/// other adjacent view).
+///
+/// This trait is implemented by functions of the form `FnMut(&T, &T) -> bool`,
+/// where the first argument is the view from which the traversal moves and the
+/// second argument is the adjacent view.
+///
+/// See [`Traversal`].
+///
+/// [`Traversal`]: crate::entity::traverse::Traversal
+pub trait Filter<T> {
+    fn filter(&mut self, from: &T, to: &T) -> bool;
+}
+
+impl<T, F> Filter<T> for F
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    fn filter(&mut self, from: &T, to: &T) -> bool {
+        (self)(from, to)
+    }
+}
+
+/// Filter that accepts all adjacent views.
+///
+/// This is the default filter of a [`Traversal`], which visits everything that
+/// is reachable.
+///
+/// [`Traversal`]: crate::entity::traverse::Traversal
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Unfiltered;
+
+impl<T> Filter<T> for Unfiltered {
+    fn filter(&mut self, _: &T, _: &T) -> bool {
+        true
+    }
+}
+
 #[derive(Debug)]
-pub struct Traversal<B, T, R = Depth>
+pub struct Traversal<B, T, R = Depth, F = Unfiltered>
 where
     B: Reborrow,
     B::Target: AsStorage<T::Entity>,
     T: Adjacency,
     R: Order<T::Key>,
+    F: Filter<T>,
 {
     storage: B,
     breadcrumbs: HashSet<T::Key>,
     buffer: R::Buffer,
+    filter: F,
     phantom: PhantomData<T>,
 }
 
-impl<B, T, R> Clone for Traversal<B, T, R>
+impl<B, T, R, F> Traversal<B, T, R, F>
+where
+    B: Reborrow,
+    B::Target: AsStorage<T::Entity>,
+    T: Adjacency + Unbind<B>,
+    R: Order<T::Key>,
+    F: Filter<T>,
+{
+    /// Constructs a traversal from the given view that only moves between
+    /// adjacent views accepted by the given filter.
+    pub fn with_filter(view: T, filter: F) -> Self {
+        let (storage, key) = view.unbind();
+        let capacity = storage.reborrow().as_storage().len();
+        let mut buffer = R::Buffer::default();
+        buffer.push(key);
+        Traversal {
+            storage,
+            breadcrumbs: HashSet::with_capacity(capacity),
+            buffer,
+            filter,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<B, T, R, F> Clone for Traversal<B, T, R, F>
 where
     B: Clone + Reborrow,
     B::Target: AsStorage<T::Entity>,
     T: Adjacency,
     R: Order<T::Key>,
     R::Buffer: Clone,
+    F: Clone + Filter<T>,
 {
     fn clone(&self) -> Self {
         Traversal {
             storage: self.storage.clone(),
             breadcrumbs: self.breadcrumbs.clone(),
             buffer: self.buffer.clone(),
+            filter: self.filter.clone(),
             phantom: PhantomData,
         }
     }
 }
 
-impl<B, T, R> From<T> for Traversal<B, T, R>
+impl<B, T, R, F> From<T> for Traversal<B, T, R, F>
 where
     B: Reborrow,
     B::Target: AsStorage<T::Entity>,
     T: Adjacency + Unbind<B>,
     R: Order<T::Key>,
+    F: Default + Filter<T>,
 {
     fn from(view: T) -> Self {
-        let (storage, key) = view.unbind();
-        let capacity = storage.reborrow().as_storage().len();
-        let mut buffer = R::Buffer::default();
-        buffer.push(key);
-        Traversal {
-            storage,
-            breadcrumbs: HashSet::with_capacity(capacity),
-            buffer,
-            phantom: PhantomData,
-        }
+        Traversal::with_filter(view, Default::default())
     }
 }
 
-impl<'a, M, T, R> Iterator for Traversal<&'a M, T, R>
+impl<'a, M, T, R, F> Iterator for Traversal<&'a M, T, R, F>
 where
     M: 'a + AsStorage<T::Entity>,
     T: Adjacency + Bind<&'a M>,
     R: Order<T::Key>,
+    F: Filter<T>,
 {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
         while let Some(view) = self.buffer.pop().and_then(|key| T::bind(self.storage, key)) {
             if self.breadcrumbs.insert(view.key()) {
-                self.buffer.extend(view.adjacency());
+                let Traversal {
+                    storage,
+                    ref breadcrumbs,
+                    ref mut buffer,
+                    ref mut filter,
+                    ..
+                } = *self;
+                // Do not query the filter for views that have already been
+                // visited.
+                buffer.extend(
+                    view.adjacency()
+                        .into_iter()
+                        .filter(|key| !breadcrumbs.contains(key))
+                        .filter(|key| {
+                            T::bind(storage, *key)
+                                .map_or(false, |adjacent| filter.filter(&view, &adjacent))
+                        }),
+                );
                 return Some(view);
             }
         }
@@ -0,0 +1,593 @@
+//! Bounding volume hierarchies.
+//!
+//! A [`Bvh`] partitions items, such as the faces of a [`MeshGraph`] or the
+//! polygons of a [`MeshBuffer`], into a binary tree of axis-aligned bounding
+//! boxes. Queries against the hierarchy only visit items with bounds that may
+//! be relevant, rather than every item.
+//!
+//! A [`Bvh`] does not borrow the data structure from which it is built and
+//! refers to items by key. When positions change but topology does not, the
+//! bounds of a [`Bvh`] can be _refit_, which is much cheaper than building a
+//! new hierarchy. When topology changes, the hierarchy must be _rebuilt_.
+//!
+//! [`Bvh`]: crate::geometry::bvh::Bvh
+//! [`MeshBuffer`]: crate::buffer::MeshBuffer
+//! [`MeshGraph`]: crate::graph::MeshGraph
+
+use decorum::cmp::IntrinsicOrd;
+use num::{One, Zero};
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+use theon::query::Aabb;
+use theon::space::{EuclideanSpace, FiniteDimensional, Scalar};
+use typenum::U3;
+
+use crate::geometry::component;
+
+/// The maximum number of items in a leaf node.
+const LEAF_CAPACITY: usize = 4;
+
+/// Bounds of an item or node, given by its lower and upper corners.
+#[derive(Clone, Copy)]
+pub(crate) struct Bounds<S>
+where
+    S: EuclideanSpace,
+{
+    pub lower: S,
+    pub upper: S,
+}
+
+impl<S> Bounds<S>
+where
+    S: EuclideanSpace + FiniteDimensional<N = U3>,
+{
+    fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = S>,
+    {
+        let mut points = points.into_iter();
+        let point = points.next()?;
+        Some(points.fold(
+            Bounds {
+                lower: point,
+                upper: point,
+            },
+            |bounds, point| {
+                bounds.union(&Bounds {
+                    lower: point,
+                    upper: point,
+                })
+            },
+        ))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let (ax, ay, az) = self.lower.into_xyz();
+        let (bx, by, bz) = other.lower.into_xyz();
+        let lower = S::from_xyz(min(ax, bx), min(ay, by), min(az, bz));
+        let (ax, ay, az) = self.upper.into_xyz();
+        let (bx, by, bz) = other.upper.into_xyz();
+        let upper = S::from_xyz(max(ax, bx), max(ay, by), max(az, bz));
+        Bounds { lower, upper }
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        (0..3).all(|axis| {
+            component(self.lower, axis) <= component(other.upper, axis)
+                && component(other.lower, axis) <= component(self.upper, axis)
+        })
+    }
+
+    /// Gets the squared distance from a point to the bounds.
+    ///
+    /// The distance is zero if the point is within the bounds.
+    pub fn distance_squared(&self, point: S) -> Scalar<S> {
+        (0..3).fold(Zero::zero(), |sum, axis| {
+            let x = component(point, axis);
+            let lower = component(self.lower, axis);
+            let upper = component(self.upper, axis);
+            let difference = if x < lower {
+                lower - x
+            }
+            else if x > upper {
+                x - upper
+            }
+            else {
+                Zero::zero()
+            };
+            sum + (difference * difference)
+        })
+    }
+
+    /// Gets the time of entry of a ray into the bounds.
+    ///
+    /// The ray is given by its origin and the point reached at unit time.
+    /// Returns `None` if the ray does not intersect the bounds. The time is
+    /// zero if the origin is within the bounds.
+    pub fn entry(&self, origin: S, endpoint: S) -> Option<Scalar<S>> {
+        let mut near = Scalar::<S>::zero();
+        let mut far: Option<Scalar<S>> = None;
+        for axis in 0..3 {
+            let x = component(origin, axis);
+            let dx = component(endpoint, axis) - x;
+            let lower = component(self.lower, axis);
+            let upper = component(self.upper, axis);
+            if dx == Zero::zero() {
+                if x < lower || x > upper {
+                    return None;
+                }
+                continue;
+            }
+            let (t0, t1) = ((lower - x) / dx, (upper - x) / dx);
+            let (t0, t1) = if t0 > t1 { (t1, t0) } else { (t0, t1) };
+            near = max(near, t0);
+            let t1 = far.map_or(t1, |far| min(far, t1));
+            if t1 < near {
+                return None;
+            }
+            far = Some(t1);
+        }
+        Some(near)
+    }
+
+    fn centroid(&self) -> S {
+        let half = Scalar::<S>::one() / (Scalar::<S>::one() + One::one());
+        self.lower + ((self.upper - self.lower) * half)
+    }
+}
+
+#[derive(Clone)]
+struct Item<K, S>
+where
+    S: EuclideanSpace,
+{
+    key: K,
+    bounds: Bounds<S>,
+}
+
+#[derive(Clone, Copy)]
+enum Children {
+    /// Indices of the left and right child nodes.
+    Branch(usize, usize),
+    /// Range of items in the leaf.
+    Leaf(usize, usize),
+}
+
+#[derive(Clone)]
+struct Node<S>
+where
+    S: EuclideanSpace,
+{
+    bounds: Bounds<S>,
+    children: Children,
+}
+
+/// Bounding volume hierarchy.
+///
+/// A `Bvh` is a binary tree of axis-aligned bounding boxes over items
+/// identified by a key `K` in a three-dimensional Euclidean space `S`. Each
+/// item is bounded by a set of points, such as the positions of the vertices
+/// of a face. Nodes are split at the median of the centroids of their items
+/// along their longest axis.
+///
+/// A `Bvh` can be built from the faces of a [`MeshGraph`] via
+/// [`MeshGraph::bvh`] or from the polygons of a [`MeshBuffer`] via
+/// [`MeshBuffer::bvh`], or can be collected from an iterator of keys and
+/// points.
+///
+/// # Examples
+///
+/// Finding pairs of faces of a cube with intersecting bounds:
+///
+/// ```rust
+/// # extern crate decorum;
+/// # extern crate nalgebra;
+/// # extern crate plexus;
+/// #
+/// use decorum::R64;
+/// use nalgebra::Point3;
+/// use plexus::graph::MeshGraph;
+/// use plexus::prelude::*;
+/// use plexus::primitive::cube::Cube;
+/// use plexus::primitive::generate::Position;
+///
+/// type E3 = Point3<R64>;
+///
+/// let graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+/// let bvh = graph.bvh();
+///
+/// // Each face intersects itself and its four adjacent faces.
+/// let pairs = bvh.intersections(&bvh);
+/// assert_eq!(30, pairs.len());
+/// ```
+///
+/// [`MeshBuffer`]: crate::buffer::MeshBuffer
+/// [`MeshBuffer::bvh`]: crate::buffer::MeshBuffer::bvh
+/// [`MeshGraph`]: crate::graph::MeshGraph
+/// [`MeshGraph::bvh`]: crate::graph::MeshGraph::bvh
+#[derive(Clone)]
+pub struct Bvh<K, S>
+where
+    S: EuclideanSpace,
+{
+    items: Vec<Item<K, S>>,
+    nodes: Vec<Node<S>>,
+}
+
+impl<K, S> Bvh<K, S>
+where
+    S: EuclideanSpace + FiniteDimensional<N = U3>,
+{
+    /// Gets the number of items in the hierarchy.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if the hierarchy has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Gets an iterator over the keys of the items in the hierarchy.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.items.iter().map(|item| &item.key)
+    }
+
+    /// Gets an axis-aligned bounding box that encloses all items.
+    ///
+    /// Returns `None` if the hierarchy has no items.
+    pub fn aabb(&self) -> Option<Aabb<S>>
+    where
+        Scalar<S>: IntrinsicOrd,
+    {
+        self.nodes.first().map(|node| to_aabb(&node.bounds))
+    }
+
+    /// Recomputes the bounds of each item and node without changing the
+    /// structure of the hierarchy.
+    ///
+    /// The given function returns the points that bound the item with the
+    /// given key. If it returns no points, then the bounds of the item are
+    /// unchanged. Refitting is appropriate when positions change but the set
+    /// of items does not. Large displacements may degrade the quality of the
+    /// hierarchy, in which case it should be rebuilt.
+    pub fn refit_with<F, I>(&mut self, mut f: F)
+    where
+        F: FnMut(&K) -> I,
+        I: IntoIterator<Item = S>,
+    {
+        for item in self.items.iter_mut() {
+            if let Some(bounds) = Bounds::from_points(f(&item.key)) {
+                item.bounds = bounds;
+            }
+        }
+        // Children are always inserted after their parents, so nodes can be
+        // refit in reverse order.
+        for index in (0..self.nodes.len()).rev() {
+            let bounds = match self.nodes[index].children {
+                Children::Branch(left, right) => {
+                    self.nodes[left].bounds.union(&self.nodes[right].bounds)
+                }
+                Children::Leaf(start, end) => union(&self.items[start..end]),
+            };
+            self.nodes[index].bounds = bounds;
+        }
+    }
+
+    /// Rebuilds the hierarchy from the given keys and points.
+    ///
+    /// This discards the structure of the hierarchy and is equivalent to
+    /// collecting a new `Bvh`. Rebuilding is necessary when items are inserted
+    /// or removed.
+    pub fn rebuild<I, J>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = (K, J)>,
+        J: IntoIterator<Item = S>,
+    {
+        *self = items.into_iter().collect();
+    }
+
+    /// Gets the keys of the items with bounds for which the given predicate
+    /// returns `true`.
+    ///
+    /// The predicate is applied to the bounds of nodes and items. The items
+    /// of a node are only visited if the predicate returns `true` for the
+    /// bounds of that node, so the predicate must also return `true` for any
+    /// bounds that enclose bounds for which it returns `true`.
+    pub fn query_with<P>(&self, mut predicate: P) -> Vec<&K>
+    where
+        Scalar<S>: IntrinsicOrd,
+        P: FnMut(&Aabb<S>) -> bool,
+    {
+        let mut keys = Vec::new();
+        self.traverse_with(|bounds| predicate(&to_aabb(bounds)), |item| keys.push(item));
+        keys
+    }
+
+    /// Gets the pairs of keys of items in this and another hierarchy with
+    /// intersecting bounds.
+    ///
+    /// This is a broad phase query: the items in each pair may or may not
+    /// intersect, but items in no other pairs intersect.
+    pub fn intersections<'a, L>(&'a self, other: &'a Bvh<L, S>) -> Vec<(&'a K, &'a L)> {
+        let mut pairs = Vec::new();
+        if self.nodes.is_empty() || other.nodes.is_empty() {
+            return pairs;
+        }
+        let mut nodes = vec![(0, 0)];
+        while let Some((a, b)) = nodes.pop() {
+            let (left, right) = (&self.nodes[a], &other.nodes[b]);
+            if !left.bounds.intersects(&right.bounds) {
+                continue;
+            }
+            match (left.children, right.children) {
+                (Children::Leaf(i, j), Children::Leaf(k, l)) => {
+                    for left in &self.items[i..j] {
+                        for right in &other.items[k..l] {
+                            if left.bounds.intersects(&right.bounds) {
+                                pairs.push((&left.key, &right.key));
+                            }
+                        }
+                    }
+                }
+                (Children::Branch(i, j), Children::Leaf(..)) => {
+                    nodes.push((i, b));
+                    nodes.push((j, b));
+                }
+                (_, Children::Branch(k, l)) => {
+                    nodes.push((a, k));
+                    nodes.push((a, l));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Visits the keys of the items with bounds for which the given predicate
+    /// returns `true`.
+    pub(crate) fn traverse_with<'a, P, F>(&'a self, mut predicate: P, mut f: F)
+    where
+        P: FnMut(&Bounds<S>) -> bool,
+        F: FnMut(&'a K),
+    {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let mut nodes = vec![0];
+        while let Some(index) = nodes.pop() {
+            let node = &self.nodes[index];
+            if !predicate(&node.bounds) {
+                continue;
+            }
+            match node.children {
+                Children::Branch(left, right) => {
+                    nodes.push(right);
+                    nodes.push(left);
+                }
+                Children::Leaf(start, end) => {
+                    for item in &self.items[start..end] {
+                        if predicate(&item.bounds) {
+                            f(&item.key);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Finds the item nearest to a point.
+    ///
+    /// The given function returns the squared distance from the point to the
+    /// item with the given key along with any output for that item, or `None`
+    /// if the item should be ignored. Nodes are visited nearest first and are
+    /// skipped if their bounds are farther than the nearest item found so far.
+    pub(crate) fn nearest_with<'a, T, F>(&'a self, point: S, mut f: F) -> Option<T>
+    where
+        F: FnMut(&'a K) -> Option<(Scalar<S>, T)>,
+    {
+        let mut nearest: Option<(Scalar<S>, T)> = None;
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let mut nodes = vec![0];
+        while let Some(index) = nodes.pop() {
+            let node = &self.nodes[index];
+            let is_farther = |bounds: &Bounds<S>, nearest: &Option<(Scalar<S>, T)>| {
+                nearest.as_ref().map_or(false, |(distance, _)| {
+                    bounds.distance_squared(point) > *distance
+                })
+            };
+            if is_farther(&node.bounds, &nearest) {
+                continue;
+            }
+            match node.children {
+                Children::Branch(left, right) => {
+                    let (left, right) = if self.nodes[left].bounds.distance_squared(point)
+                        <= self.nodes[right].bounds.distance_squared(point)
+                    {
+                        (left, right)
+                    }
+                    else {
+                        (right, left)
+                    };
+                    nodes.push(right);
+                    nodes.push(left);
+                }
+                Children::Leaf(start, end) => {
+                    for item in &self.items[start..end] {
+                        if is_farther(&item.bounds, &nearest) {
+                            continue;
+                        }
+                        if let Some((distance, output)) = f(&item.key) {
+                            if nearest
+                                .as_ref()
+                                .map_or(true, |(nearest, _)| distance < *nearest)
+                            {
+                                nearest = Some((distance, output));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        nearest.map(|(_, output)| output)
+    }
+
+    /// Inserts nodes for the items in the given range and returns the index of
+    /// the root of those nodes.
+    fn partition(&mut self, start: usize, end: usize) -> usize {
+        let bounds = union(&self.items[start..end]);
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            bounds,
+            children: Children::Leaf(start, end),
+        });
+        if end - start <= LEAF_CAPACITY {
+            return index;
+        }
+        let centroids = Bounds::from_points(
+            self.items[start..end]
+                .iter()
+                .map(|item| item.bounds.centroid()),
+        )
+        .expect("empty node");
+        let axis = (0..3)
+            .max_by(|a: &usize, b: &usize| {
+                let a = component(centroids.upper, *a) - component(centroids.lower, *a);
+                let b = component(centroids.upper, *b) - component(centroids.lower, *b);
+                a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+            })
+            .expect("no axes");
+        if component(centroids.upper, axis) <= component(centroids.lower, axis) {
+            // The centroids converge and cannot be split.
+            return index;
+        }
+        self.items[start..end].sort_unstable_by(|a, b| {
+            let a = component(a.bounds.centroid(), axis);
+            let b = component(b.bounds.centroid(), axis);
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        });
+        let middle = start + ((end - start) / 2);
+        let left = self.partition(start, middle);
+        let right = self.partition(middle, end);
+        self.nodes[index].children = Children::Branch(left, right);
+        index
+    }
+}
+
+impl<K, S, I> FromIterator<(K, I)> for Bvh<K, S>
+where
+    S: EuclideanSpace + FiniteDimensional<N = U3>,
+    I: IntoIterator<Item = S>,
+{
+    /// Builds a hierarchy from keys and the points that bound their items.
+    ///
+    /// Items with no points are discarded.
+    fn from_iter<J>(input: J) -> Self
+    where
+        J: IntoIterator<Item = (K, I)>,
+    {
+        let items = input
+            .into_iter()
+            .filter_map(|(key, points)| {
+                Bounds::from_points(points).map(|bounds| Item { key, bounds })
+            })
+            .collect::<Vec<_>>();
+        let mut bvh = Bvh {
+            nodes: Vec::with_capacity(items.len()),
+            items,
+        };
+        if !bvh.items.is_empty() {
+            bvh.partition(0, bvh.items.len());
+        }
+        bvh
+    }
+}
+
+fn union<K, S>(items: &[Item<K, S>]) -> Bounds<S>
+where
+    S: EuclideanSpace + FiniteDimensional<N = U3>,
+{
+    let (first, items) = items.split_first().expect("empty node");
+    items
+        .iter()
+        .fold(first.bounds, |bounds, item| bounds.union(&item.bounds))
+}
+
+fn to_aabb<S>(bounds: &Bounds<S>) -> Aabb<S>
+where
+    S: EuclideanSpace,
+    Scalar<S>: IntrinsicOrd,
+{
+    Aabb::from_points(vec![bounds.lower, bounds.upper].into_iter())
+}
+
+fn min<T>(a: T, b: T) -> T
+where
+    T: PartialOrd,
+{
+    if b < a {
+        b
+    }
+    else {
+        a
+    }
+}
+
+fn max<T>(a: T, b: T) -> T
+where
+    T: PartialOrd,
+{
+    if b > a {
+        b
+    }
+    else {
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{Point3, Vector3};
+
+    use crate::buffer::MeshBuffer4;
+    use crate::graph::{GraphError, MeshGraph};
+    use crate::prelude::*;
+    use crate::primitive::cube::Cube;
+    use crate::primitive::generate::Position;
+
+    type E3 = Point3<f64>;
+
+    #[test]
+    fn refit_and_rebuild() {
+        let mut graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+        let fixed = graph.bvh();
+        let mut bvh = graph.bvh();
+        assert_eq!(6, bvh.len());
+        assert_eq!(30, fixed.intersections(&bvh).len());
+
+        // Move the cube away from its original position.
+        for mut vertex in graph.vertex_orphans() {
+            *vertex.get_mut() += Vector3::new(10.0, 0.0, 0.0);
+        }
+        graph.refit_bvh(&mut bvh).unwrap();
+        assert!(fixed.intersections(&bvh).is_empty());
+        assert_eq!(30, bvh.intersections(&bvh).len());
+
+        // Refitting fails after topology changes.
+        let key = graph.faces().next().unwrap().key();
+        graph.face_mut(key).unwrap().triangulate();
+        assert_eq!(Err(GraphError::TopologyConflict), graph.refit_bvh(&mut bvh));
+        bvh = graph.bvh();
+        assert_eq!(7, bvh.len());
+    }
+
+    #[test]
+    fn buffer_bvh() {
+        let buffer: MeshBuffer4<usize, E3> = Cube::new().polygons::<Position<E3>>().collect();
+        let bvh = buffer.bvh();
+
+        assert_eq!(6, bvh.len());
+        assert_eq!(30, bvh.intersections(&bvh).len());
+    }
+}
@@ -10,13 +10,19 @@
 //! [`integration`]: crate::integration
 
 use num::{One, Zero};
+use theon::space::{EuclideanSpace, FiniteDimensional};
+use typenum::U3;
 
+pub mod bvh;
 pub mod partition;
+pub(crate) mod tangent;
 
 pub use theon::query::*;
 pub use theon::space::{Scalar, Vector};
 pub use theon::{AsPosition, AsPositionMut, Position};
 
+pub use crate::geometry::tangent::Tangent;
+
 pub trait FromGeometry<T> {
     fn from_geometry(other: T) -> Self;
 }
@@ -88,3 +94,19 @@ where
 pub trait Metric: Eq + One + Ord + Zero {}
 
 impl<Q> Metric for Q where Q: Eq + One + Ord + Zero {}
+
+/// Gets the component of a point along the given axis.
+///
+/// Axes zero, one, and two correspond to $x$, $y$, and $z$, respectively.
+/// Other axes are treated as $z$.
+pub(crate) fn component<S>(point: S, axis: usize) -> Scalar<S>
+where
+    S: EuclideanSpace + FiniteDimensional<N = U3>,
+{
+    let (x, y, z) = point.into_xyz();
+    match axis {
+        0 => x,
+        1 => y,
+        _ => z,
+    }
+}
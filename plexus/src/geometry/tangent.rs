@@ -0,0 +1,202 @@
+//! Tangent spaces for normal mapping.
+//!
+//! Tangents are computed in the manner of MikkTSpace: the tangent frame of each
+//! corner of a triangle is projected into the tangent plane of its normal and
+//! the frames of corners that share a vertex (and its normal, texture
+//! coordinates, and handedness) are weighted by the angles of the corners and
+//! summed. The bitangent is not stored, but is instead given by the sign of its
+//! orientation with respect to the normal and tangent. Corners with mirrored
+//! texture coordinates have opposite handedness and are only split when
+//! tangents are computed per corner; tangents computed per vertex cannot be
+//! split, so mirrored corners should not share vertices in that case.
+
+use decorum::Real;
+use num::{One, Zero};
+use theon::ops::{Cross, Dot};
+use theon::space::{EuclideanSpace, InnerSpace, Scalar, Vector, VectorSpace};
+
+/// Tangent with the sign of its bitangent.
+///
+/// The bitangent is given by the cross product of the normal and tangent
+/// scaled by the sign, as in MikkTSpace and glTF. The sign is either one or
+/// negative one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tangent<V>
+where
+    V: VectorSpace,
+{
+    /// The normalized tangent. The tangent is orthogonal to the normal.
+    pub vector: V,
+    /// The sign of the bitangent.
+    pub sign: V::Scalar,
+}
+
+impl<V> Tangent<V>
+where
+    V: Cross<Output = V> + VectorSpace,
+{
+    /// Gets the bitangent with respect to the given normal.
+    pub fn bitangent(&self, normal: V) -> V {
+        normal.cross(self.vector) * self.sign
+    }
+}
+
+/// Accumulates the tangent frames of corners that share a vertex.
+pub(crate) struct TangentAccumulator<S>
+where
+    S: EuclideanSpace,
+{
+    normal: Vector<S>,
+    tangent: Vector<S>,
+    bitangent: Vector<S>,
+    fallback: Option<Vector<S>>,
+}
+
+impl<S> TangentAccumulator<S>
+where
+    S: EuclideanSpace,
+    Vector<S>: Cross<Output = Vector<S>>,
+{
+    pub fn new(normal: Vector<S>) -> Self {
+        TangentAccumulator {
+            normal,
+            tangent: Zero::zero(),
+            bitangent: Zero::zero(),
+            fallback: None,
+        }
+    }
+
+    /// Gets the handedness of the corner of a triangle.
+    ///
+    /// The positions and texture coordinates are given in winding order
+    /// beginning with the vertex of the corner. Returns one or negative one
+    /// with the sign of the bitangent of the corner or zero if the texture
+    /// coordinates of the corner are degenerate.
+    pub fn handedness(
+        normal: Vector<S>,
+        positions: [S; 3],
+        uvs: [(Scalar<S>, Scalar<S>); 3],
+    ) -> Scalar<S> {
+        let zero = Scalar::<S>::zero();
+        let one = Scalar::<S>::one();
+        let [a, b, c] = positions;
+        let ((ua, va), (ub, vb), (uc, vc)) = (uvs[0], uvs[1], uvs[2]);
+        // The cross product of the tangent and bitangent of the corner is the
+        // cross product of its edges divided by the determinant of its texture
+        // coordinates.
+        let determinant = ((ub - ua) * (vc - va)) - ((uc - ua) * (vb - va));
+        let orientation = normal.dot((b - a).cross(c - a)) * determinant;
+        if orientation > zero {
+            one
+        }
+        else if orientation < zero {
+            -one
+        }
+        else {
+            zero
+        }
+    }
+
+    /// Inserts the corner of a triangle.
+    ///
+    /// The positions and texture coordinates are given in winding order
+    /// beginning with the vertex of the corner.
+    pub fn insert(&mut self, positions: [S; 3], uvs: [(Scalar<S>, Scalar<S>); 3]) {
+        let zero = Scalar::<S>::zero();
+        let one = Scalar::<S>::one();
+        let [a, b, c] = positions;
+        let ((ua, va), (ub, vb), (uc, vc)) = (uvs[0], uvs[1], uvs[2]);
+        let ab = b - a;
+        let ac = c - a;
+        if self.fallback.is_none() {
+            self.fallback = self.project(ab);
+        }
+        let (du1, dv1) = (ub - ua, vb - va);
+        let (du2, dv2) = (uc - ua, vc - va);
+        let determinant = (du1 * dv2) - (du2 * dv1);
+        if determinant == zero {
+            return;
+        }
+        let tangent = self.project(((ab * dv2) - (ac * dv1)) * (one / determinant));
+        let bitangent = self.project(((ac * du1) - (ab * du2)) * (one / determinant));
+        let (tangent, bitangent) = match (tangent, bitangent) {
+            (Some(tangent), Some(bitangent)) => (tangent, bitangent),
+            _ => return,
+        };
+        let (lab, lac) = (ab.magnitude(), ac.magnitude());
+        if lab <= zero || lac <= zero {
+            return;
+        }
+        let cos = ab.dot(ac) / (lab * lac);
+        let angle = Real::acos(if cos > one {
+            one
+        }
+        else if cos < -one {
+            -one
+        }
+        else {
+            cos
+        });
+        self.tangent = self.tangent + (tangent * angle);
+        self.bitangent = self.bitangent + (bitangent * angle);
+    }
+
+    /// Gets the tangent.
+    ///
+    /// Returns `None` if no tangent orthogonal to the normal could be computed.
+    /// If the texture coordinates of all corners are degenerate, then the
+    /// tangent is aligned with an edge of the first corner.
+    pub fn into_tangent(self) -> Option<Tangent<Vector<S>>> {
+        let one = Scalar::<S>::one();
+        let vector = self.project(self.tangent).or(self.fallback)?;
+        let sign = if self.normal.cross(vector).dot(self.bitangent) < Zero::zero() {
+            -one
+        }
+        else {
+            one
+        };
+        Some(Tangent { vector, sign })
+    }
+
+    /// Projects a vector into the tangent plane and normalizes it.
+    fn project(&self, vector: Vector<S>) -> Option<Vector<S>> {
+        let normal = self.normal.normalize()?;
+        let vector = vector - (normal * normal.dot(vector));
+        if vector.magnitude() > Zero::zero() {
+            vector.normalize()
+        }
+        else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{Point3, Vector3};
+
+    use crate::geometry::tangent::TangentAccumulator;
+
+    #[test]
+    fn mirrored_tangent() {
+        let positions = [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+        ];
+        let mut tangent = TangentAccumulator::new(Vector3::<f64>::z());
+        tangent.insert(positions, [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]);
+        let tangent = tangent.into_tangent().unwrap();
+
+        assert!((tangent.vector - Vector3::x()).norm() < 1e-9);
+        assert!((tangent.sign - 1.0).abs() < 1e-9);
+
+        // Mirror the texture coordinates about the vertical axis.
+        let mut tangent = TangentAccumulator::new(Vector3::<f64>::z());
+        tangent.insert(positions, [(1.0, 0.0), (0.0, 0.0), (1.0, 1.0)]);
+        let tangent = tangent.into_tangent().unwrap();
+
+        assert!((tangent.vector + Vector3::x()).norm() < 1e-9);
+        assert!((tangent.sign + 1.0).abs() < 1e-9);
+    }
+}
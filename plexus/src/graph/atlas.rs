@@ -0,0 +1,438 @@
+//! Seams, charts, and texture atlases.
+//!
+//! A graph is cut into charts by duplicating vertices along seam edges. Each
+//! chart must be topologically equivalent to a disk so that it can be
+//! parameterized and packed into an atlas.
+
+use decorum::Real;
+use num::{One, Zero};
+use smallvec::SmallVec;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+use theon::space::{EuclideanSpace, Scalar};
+use theon::AsPosition;
+
+use crate::builder::{Buildable, FacetBuilder, MeshBuilder, SurfaceBuilder};
+use crate::graph::data::GraphData;
+use crate::graph::edge::{ArcKey, EdgeKey, EdgeView};
+use crate::graph::geometry::{self, VertexPosition};
+use crate::graph::parameterize::{self, Parameterization, Pinning};
+use crate::graph::topology;
+use crate::graph::vertex::VertexKey;
+use crate::graph::{GraphError, MeshGraph, OptionExt as _};
+
+type Uv<T> = (T, T);
+
+/// Selects seam edges that cut each disjoint sub-graph into a disk.
+///
+/// The seams of a sub-graph are the edges that are not crossed by a spanning
+/// tree of its faces. Branches that do not form loops are pruned, because
+/// cutting along them does not change the topology of the sub-graph. Closed
+/// sub-graphs with genus zero retain a path of two edges. Boundary edges are
+/// never seams.
+pub(in crate::graph) fn seams<G>(graph: &MeshGraph<G>) -> Vec<EdgeKey>
+where
+    G: GraphData,
+{
+    let mut seams = Vec::new();
+    for vertex in graph.disjoint_subgraph_vertices() {
+        // The seams of a closed sub-graph with genus zero form a tree, which
+        // would otherwise be pruned entirely.
+        let invariants = topology::invariants(graph, vertex.key());
+        let limit = if invariants.boundary_count() == 0 && invariants.genus() == Some(0) {
+            2
+        }
+        else {
+            0
+        };
+        let mut edges = HashSet::new();
+        let mut faces = HashSet::new();
+        for vertex in vertex.traverse_by_depth() {
+            for arc in vertex.outgoing_arcs() {
+                edges.insert(arc.edge().key());
+                if let Some(face) = arc.face() {
+                    faces.insert(face.key());
+                }
+            }
+        }
+        // Remove the edges crossed by a spanning tree of the faces.
+        let mut visited = HashSet::with_capacity(faces.len());
+        if let Some(key) = faces.iter().cloned().next() {
+            let mut queue = VecDeque::new();
+            visited.insert(key);
+            queue.push_back(key);
+            while let Some(key) = queue.pop_front() {
+                for arc in graph.face(key).expect_consistent().adjacent_arcs() {
+                    if let Some(face) = arc.opposite_arc().face() {
+                        if visited.insert(face.key()) {
+                            edges.remove(&arc.edge().key());
+                            queue.push_back(face.key());
+                        }
+                    }
+                }
+            }
+        }
+        // Prune branches, beginning with edges that have a leaf vertex.
+        let mut incidence = HashMap::<VertexKey, HashSet<EdgeKey>>::new();
+        for key in edges.iter() {
+            let arc = graph.edge(*key).expect_consistent().into_arc();
+            for vertex in &[arc.source_vertex().key(), arc.destination_vertex().key()] {
+                incidence.entry(*vertex).or_default().insert(*key);
+            }
+        }
+        let mut leaves = incidence
+            .iter()
+            .filter(|(_, edges)| edges.len() == 1)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        while edges.len() > limit {
+            let leaf = if let Some(leaf) = leaves.pop() {
+                leaf
+            }
+            else {
+                break;
+            };
+            let key = if let Some(key) = incidence[&leaf].iter().cloned().next() {
+                key
+            }
+            else {
+                continue;
+            };
+            edges.remove(&key);
+            let arc = graph.edge(key).expect_consistent().into_arc();
+            for vertex in &[arc.source_vertex().key(), arc.destination_vertex().key()] {
+                let incident = incidence.get_mut(vertex).expect_consistent();
+                incident.remove(&key);
+                if incident.len() == 1 {
+                    leaves.push(*vertex);
+                }
+            }
+        }
+        seams.extend(edges.into_iter().filter(|key| {
+            let arc = graph.edge(*key).expect_consistent().into_arc();
+            !arc.is_boundary_arc() && !arc.opposite_arc().is_boundary_arc()
+        }));
+    }
+    seams
+}
+
+/// Cuts a graph along seam edges.
+///
+/// Returns the cut graph and a mapping from each of its vertices to the vertex
+/// in the original graph from which it was copied.
+pub(in crate::graph) fn cut<G>(
+    graph: &MeshGraph<G>,
+    seams: &HashSet<EdgeKey>,
+) -> Result<(MeshGraph<G>, HashMap<VertexKey, VertexKey>), GraphError>
+where
+    G: GraphData,
+{
+    for key in seams {
+        graph.edge(*key).ok_or(GraphError::TopologyNotFound)?;
+    }
+    // Each wedge of corners becomes a vertex in the cut graph.
+    let wedges = wedges(graph, |edge| seams.contains(&edge.key()));
+    let mut keys = HashMap::with_capacity(wedges.len());
+    let mut sources = HashMap::with_capacity(wedges.len());
+    let mut builder = MeshGraph::<G>::builder();
+    builder.surface_with(|builder| {
+        for vertex in graph.vertices() {
+            if vertex.adjacent_faces().next().is_none() {
+                let key = builder.insert_vertex(vertex.get().clone())?;
+                sources.insert(key, vertex.key());
+            }
+        }
+        for face in graph.faces() {
+            let indices = face
+                .adjacent_arcs()
+                .map(|arc| {
+                    let wedge = wedges[&arc.key()];
+                    if let Some(key) = keys.get(&wedge) {
+                        Ok(*key)
+                    }
+                    else {
+                        let vertex = arc.source_vertex();
+                        let key = builder.insert_vertex(vertex.get().clone())?;
+                        keys.insert(wedge, key);
+                        sources.insert(key, vertex.key());
+                        Ok(key)
+                    }
+                })
+                .collect::<Result<SmallVec<[_; 8]>, GraphError>>()?;
+            builder.facets_with(|builder| {
+                builder.insert_facet(indices.as_slice(), face.get().clone())
+            })?;
+        }
+        Ok::<_, GraphError>(())
+    })?;
+    Ok((builder.build()?, sources))
+}
+
+/// Joins the corners of faces about each vertex into wedges.
+///
+/// Each corner of a face is identified by the arc in that face that leaves the
+/// vertex of the corner. Corners about a vertex are joined into a wedge if
+/// they are connected by an edge for which the given predicate returns
+/// `false`. Returns a mapping from each corner to the representative corner
+/// of its wedge.
+pub(in crate::graph) fn wedges<G, P>(
+    graph: &MeshGraph<G>,
+    mut is_split: P,
+) -> HashMap<ArcKey, ArcKey>
+where
+    G: GraphData,
+    P: FnMut(EdgeView<&MeshGraph<G>>) -> bool,
+{
+    let mut wedges = graph
+        .arcs()
+        .filter(|arc| !arc.is_boundary_arc())
+        .map(|arc| (arc.key(), arc.key()))
+        .collect::<HashMap<_, _>>();
+    for arc in graph.arcs().filter(|arc| !arc.is_boundary_arc()) {
+        let previous = arc.previous_arc();
+        if is_split(previous.edge()) {
+            continue;
+        }
+        let opposite = previous.opposite_arc();
+        if !opposite.is_boundary_arc() {
+            let a = find(&mut wedges, arc.key());
+            let b = find(&mut wedges, opposite.key());
+            wedges.insert(a, b);
+        }
+    }
+    let keys = wedges.keys().cloned().collect::<Vec<_>>();
+    for key in keys {
+        find(&mut wedges, key);
+    }
+    wedges
+}
+
+/// Parameterizes each disjoint sub-graph as a chart and packs the charts into
+/// the unit square.
+///
+/// Charts are scaled so that the ratio of their surface and parametric areas
+/// is uniform and are packed into rows (shelves) ordered by height. The given
+/// padding is approximately the distance between charts in the unit square.
+/// Sub-graphs without faces are ignored.
+pub(in crate::graph) fn pack<G>(
+    graph: &MeshGraph<G>,
+    method: Parameterization,
+    pinning: Pinning,
+    padding: Scalar<VertexPosition<G>>,
+) -> Result<HashMap<VertexKey, Uv<Scalar<VertexPosition<G>>>>, GraphError>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace,
+{
+    struct Chart<T> {
+        uvs: HashMap<VertexKey, Uv<T>>,
+        minimum: Uv<T>,
+        scale: T,
+        width: T,
+        height: T,
+    }
+
+    let zero = Scalar::<VertexPosition<G>>::zero();
+    let one = Scalar::<VertexPosition<G>>::one();
+    let two = one + one;
+    let abs = |x: Scalar<VertexPosition<G>>| if x < zero { -x } else { x };
+    let max = |a: Scalar<VertexPosition<G>>, b: Scalar<VertexPosition<G>>| {
+        if a > b {
+            a
+        }
+        else {
+            b
+        }
+    };
+    let mut charts = Vec::new();
+    for vertex in graph.disjoint_subgraph_vertices() {
+        if vertex.adjacent_faces().next().is_none() {
+            continue;
+        }
+        let uvs = parameterize::parameterize(graph, vertex.key(), method, pinning)?;
+        let faces = uvs
+            .keys()
+            .flat_map(|key| graph.vertex(*key).expect_consistent().into_adjacent_faces())
+            .map(|face| face.key())
+            .collect::<HashSet<_>>();
+        let mut area = zero;
+        let mut parametric_area = zero;
+        for face in faces
+            .into_iter()
+            .map(|key| graph.face(key).expect_consistent())
+        {
+            let ring = face
+                .adjacent_vertices()
+                .map(|vertex| (*vertex.position(), uvs[&vertex.key()]))
+                .collect::<Vec<_>>();
+            let (p0, (u0, v0)) = ring[0];
+            for pair in ring[1..].windows(2) {
+                let ((p1, (u1, v1)), (p2, (u2, v2))) = (pair[0], pair[1]);
+                area = area + geometry::area(p1 - p0, p2 - p0);
+                parametric_area = parametric_area
+                    + (abs(((u1 - u0) * (v2 - v0)) - ((u2 - u0) * (v1 - v0))) / two);
+            }
+        }
+        if parametric_area <= zero {
+            return Err(GraphError::Geometry);
+        }
+        let scale = Real::sqrt(area / parametric_area);
+        let mut minimum = *uvs.values().next().expect_consistent();
+        let mut maximum = minimum;
+        for (u, v) in uvs.values() {
+            if *u < minimum.0 {
+                minimum.0 = *u;
+            }
+            if *v < minimum.1 {
+                minimum.1 = *v;
+            }
+            maximum = (max(maximum.0, *u), max(maximum.1, *v));
+        }
+        charts.push(Chart {
+            uvs,
+            minimum,
+            scale,
+            width: (maximum.0 - minimum.0) * scale,
+            height: (maximum.1 - minimum.1) * scale,
+        });
+    }
+    charts.sort_by(|a, b| b.height.partial_cmp(&a.height).unwrap_or(Ordering::Equal));
+    // Approximate the side of the atlas by the area of the charts. The width
+    // of the shelves must be at least the width of the widest chart.
+    let area = charts
+        .iter()
+        .fold(zero, |area, chart| area + (chart.width * chart.height));
+    let gap = padding * Real::sqrt(area);
+    let area = charts.iter().fold(zero, |area, chart| {
+        area + ((chart.width + gap) * (chart.height + gap))
+    });
+    let side = charts.iter().fold(Real::sqrt(area), |side, chart| {
+        max(side, chart.width + gap + gap)
+    });
+    let mut uvs = HashMap::new();
+    let mut offsets = Vec::with_capacity(charts.len());
+    let (mut x, mut y, mut shelf) = (gap, gap, zero);
+    let mut extent = zero;
+    for chart in charts.iter() {
+        if x > gap && x + chart.width + gap > side {
+            x = gap;
+            y = y + shelf + gap;
+            shelf = zero;
+        }
+        offsets.push((x, y));
+        x = x + chart.width + gap;
+        shelf = max(shelf, chart.height);
+        extent = max(extent, max(x, y + shelf + gap));
+    }
+    if extent <= zero {
+        return Ok(uvs);
+    }
+    for (chart, (x, y)) in charts.into_iter().zip(offsets) {
+        let Chart {
+            uvs: chart,
+            minimum,
+            scale,
+            ..
+        } = chart;
+        uvs.extend(chart.into_iter().map(|(key, (u, v))| {
+            (
+                key,
+                (
+                    (((u - minimum.0) * scale) + x) / extent,
+                    (((v - minimum.1) * scale) + y) / extent,
+                ),
+            )
+        }));
+    }
+    Ok(uvs)
+}
+
+/// Finds the representative wedge of a corner.
+fn find(wedges: &mut HashMap<ArcKey, ArcKey>, key: ArcKey) -> ArcKey {
+    let mut root = key;
+    while wedges[&root] != root {
+        root = wedges[&root];
+    }
+    // Compress the path to the representative.
+    let mut key = key;
+    while key != root {
+        key = wedges.insert(key, root).expect_consistent();
+    }
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Point3;
+
+    use crate::graph::{fixture, MeshGraph, Parameterization, Pinning};
+    use crate::prelude::*;
+    use crate::primitive::cube::Cube;
+    use crate::primitive::generate::Position;
+
+    type E3 = Point3<f64>;
+
+    #[test]
+    fn cut_along_seams() {
+        let graph = fixture::grid();
+        // The grid is already a disk and requires no seams.
+        assert!(graph.seams().is_empty());
+
+        // Cut from the boundary to the center of the grid.
+        let seam = graph
+            .edges()
+            .find(|edge| {
+                let arc = edge.arc();
+                let mut positions = [
+                    *arc.source_vertex().position(),
+                    *arc.destination_vertex().position(),
+                ];
+                positions.sort_by(|a, b| a.y.partial_cmp(&b.y).unwrap());
+                positions == [Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0)]
+            })
+            .unwrap()
+            .key();
+        let (cut, sources) = graph.cut(Some(seam)).unwrap();
+
+        // Only the boundary vertex of the seam is copied.
+        assert_eq!(10, cut.vertex_count());
+        assert_eq!(4, cut.face_count());
+        assert_eq!(10, sources.len());
+        let boundary = graph
+            .vertices()
+            .find(|vertex| *vertex.position() == Point3::new(1.0, 0.0, 0.0))
+            .unwrap()
+            .key();
+        assert_eq!(2, sources.values().filter(|key| **key == boundary).count());
+        assert_eq!(1, cut.invariants().boundary_count());
+    }
+
+    #[test]
+    fn cut_closed_graph_into_disk() {
+        let graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+        let seams = graph.seams();
+        let (cut, _) = graph.cut(seams).unwrap();
+
+        assert_eq!(6, cut.face_count());
+        assert!(cut.vertex_count() > graph.vertex_count());
+        let invariants = cut.invariants();
+        assert_eq!(1, invariants.component_count());
+        assert_eq!(1, invariants.boundary_count());
+        assert_eq!(Some(0), invariants.genus());
+    }
+
+    #[test]
+    fn pack_atlas() {
+        let graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+        let (cut, _) = graph.cut(graph.seams()).unwrap();
+        let uvs = cut
+            .atlas(Parameterization::Tutte, Pinning::Circle, 0.01)
+            .unwrap();
+
+        assert_eq!(cut.vertex_count(), uvs.len());
+        assert!(uvs
+            .values()
+            .all(|(u, v)| (0.0..=1.0).contains(u) && (0.0..=1.0).contains(v)));
+    }
+}
@@ -0,0 +1,509 @@
+//! Closest points and signed distances.
+//!
+//! Faces are treated as triangulations of their rings, as in ray casting.
+//! Signed distances are computed with angle-weighted pseudo-normals (Bærentzen
+//! and Aanæs) of the feature of the surface on which the closest point lies,
+//! which gives the correct sign for any point near a closed and consistently
+//! oriented surface.
+
+use decorum::Real;
+use num::Zero;
+use smallvec::SmallVec;
+use theon::ops::{Cross, Dot};
+use theon::space::{EuclideanSpace, FiniteDimensional, InnerSpace, Scalar, Vector};
+use theon::AsPosition;
+use typenum::U3;
+
+use crate::geometry::bvh::Bvh;
+use crate::graph::data::GraphData;
+use crate::graph::edge::{ArcKey, EdgeKey};
+use crate::graph::face::{FaceKey, FaceView};
+use crate::graph::geometry::{self, VertexPosition};
+use crate::graph::vertex::VertexKey;
+use crate::graph::{GraphError, MeshGraph, OptionExt as _};
+
+/// Feature of a surface on which a point lies.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Feature {
+    Vertex(VertexKey),
+    Edge(EdgeKey),
+    Face(FaceKey),
+}
+
+/// Point on the surface of a graph that is closest to a query point.
+#[derive(Clone)]
+pub struct ClosestPoint<S>
+where
+    S: EuclideanSpace,
+{
+    /// The face on which the closest point lies.
+    pub face: FaceKey,
+    /// The feature of the face on which the closest point lies.
+    ///
+    /// Points within a face, including along the diagonals of its
+    /// triangulation, lie on the face itself.
+    pub feature: Feature,
+    /// The closest point.
+    pub position: S,
+    /// The (unsigned) distance from the query point to the closest point.
+    pub distance: Scalar<S>,
+}
+
+/// Region of a triangulated face.
+///
+/// Indices refer to the ring of the face.
+#[derive(Clone, Copy)]
+enum Region {
+    /// The vertex of the ring.
+    Corner(usize),
+    /// The segment between two vertices of the ring. This is either an edge of
+    /// the face or a diagonal of its triangulation.
+    Segment(usize, usize),
+    /// The interior of a triangle of the triangulation.
+    Interior([usize; 3]),
+}
+
+/// Triangulation of the ring of a face.
+struct Triangulation<S>
+where
+    S: EuclideanSpace,
+{
+    face: FaceKey,
+    ring: SmallVec<[(ArcKey, VertexKey, S); 8]>,
+    triangles: SmallVec<[[usize; 3]; 8]>,
+}
+
+impl<S> Triangulation<S>
+where
+    S: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<S>: Cross<Output = Vector<S>>,
+{
+    fn from_face<G>(face: FaceView<&MeshGraph<G>>) -> Self
+    where
+        G: GraphData,
+        G::Vertex: AsPosition<Position = S>,
+    {
+        let ring = face
+            .adjacent_arcs()
+            .map(|arc| {
+                let vertex = arc.source_vertex();
+                (arc.key(), vertex.key(), *vertex.position())
+            })
+            .collect::<SmallVec<[_; 8]>>();
+        let triangles = geometry::triangulate(
+            &ring
+                .iter()
+                .map(|(_, _, position)| *position)
+                .collect::<SmallVec<[_; 8]>>(),
+        );
+        Triangulation {
+            face: face.key(),
+            ring,
+            triangles,
+        }
+    }
+
+    /// Gets the positions of the vertices of a triangle.
+    fn positions(&self, triangle: [usize; 3]) -> [S; 3] {
+        [
+            self.ring[triangle[0]].2,
+            self.ring[triangle[1]].2,
+            self.ring[triangle[2]].2,
+        ]
+    }
+
+    /// Gets the unit normal of a triangle or zero if it is degenerate.
+    fn normal(&self, triangle: [usize; 3]) -> Vector<S> {
+        let [a, b, c] = self.positions(triangle);
+        (b - a).cross(c - a).normalize().unwrap_or_else(Zero::zero)
+    }
+
+    /// Gets the index of the arc in the ring that connects the given vertices
+    /// of the ring or `None` if the vertices are joined by a diagonal.
+    fn arc(&self, a: usize, b: usize) -> Option<usize> {
+        let n = self.ring.len();
+        if b == (a + 1) % n {
+            Some(a)
+        }
+        else if a == (b + 1) % n {
+            Some(b)
+        }
+        else {
+            None
+        }
+    }
+
+    /// Gets the sum of the normals of the triangles that include both of the
+    /// given vertices of the ring.
+    fn segment_normal(&self, a: usize, b: usize) -> Vector<S> {
+        self.triangles
+            .iter()
+            .filter(|triangle| triangle.contains(&a) && triangle.contains(&b))
+            .fold(Zero::zero(), |normal, triangle| {
+                normal + self.normal(*triangle)
+            })
+    }
+
+    /// Gets the sum of the normals of the triangles that include the given
+    /// vertex of the ring weighted by the angles of those triangles at the
+    /// vertex.
+    fn corner_normal(&self, index: usize) -> Vector<S> {
+        self.triangles
+            .iter()
+            .filter_map(|triangle| {
+                let corner = triangle.iter().position(|other| *other == index)?;
+                let positions = self.positions(*triangle);
+                let (a, b, c) = (
+                    positions[corner],
+                    positions[(corner + 1) % 3],
+                    positions[(corner + 2) % 3],
+                );
+                Some(self.normal(*triangle) * geometry::angle(b - a, c - a))
+            })
+            .fold(Zero::zero(), |normal, next| normal + next)
+    }
+
+    /// Finds the point on the triangulation closest to the given point.
+    ///
+    /// Returns the squared distance, closest point, and region of the
+    /// triangulation.
+    fn closest(&self, point: S) -> (Scalar<S>, S, Region) {
+        self.triangles
+            .iter()
+            .map(|triangle| {
+                let (position, region) = closest(point, self.positions(*triangle));
+                let region = match region {
+                    Region::Corner(index) => Region::Corner(triangle[index]),
+                    Region::Segment(a, b) => Region::Segment(triangle[a], triangle[b]),
+                    Region::Interior(_) => Region::Interior(*triangle),
+                };
+                let distance = (point - position).dot(point - position);
+                (distance, position, region)
+            })
+            .fold(
+                None,
+                |nearest: Option<(Scalar<S>, S, Region)>, candidate| match nearest {
+                    Some(nearest) if nearest.0 <= candidate.0 => Some(nearest),
+                    _ => Some(candidate),
+                },
+            )
+            .expect_consistent()
+    }
+}
+
+/// Finds the point on a triangle closest to the given point.
+///
+/// This is the region-based method described by Ericson. Regions are
+/// expressed in terms of the indices of the vertices of the triangle.
+fn closest<S>(point: S, triangle: [S; 3]) -> (S, Region)
+where
+    S: EuclideanSpace,
+{
+    let zero = Scalar::<S>::zero();
+    let [a, b, c] = triangle;
+    let ab = b - a;
+    let ac = c - a;
+    let ap = point - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= zero && d2 <= zero {
+        return (a, Region::Corner(0));
+    }
+    let bp = point - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= zero && d4 <= d3 {
+        return (b, Region::Corner(1));
+    }
+    let vc = (d1 * d4) - (d3 * d2);
+    if vc <= zero && d1 >= zero && d3 <= zero {
+        return (a + (ab * (d1 / (d1 - d3))), Region::Segment(0, 1));
+    }
+    let cp = point - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= zero && d5 <= d6 {
+        return (c, Region::Corner(2));
+    }
+    let vb = (d5 * d2) - (d1 * d6);
+    if vb <= zero && d2 >= zero && d6 <= zero {
+        return (a + (ac * (d2 / (d2 - d6))), Region::Segment(0, 2));
+    }
+    let va = (d3 * d6) - (d5 * d4);
+    if va <= zero && (d4 - d3) >= zero && (d5 - d6) >= zero {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return (b + ((c - b) * w), Region::Segment(1, 2));
+    }
+    let sum = va + vb + vc;
+    if sum == zero {
+        // The triangle is degenerate.
+        return (a, Region::Corner(0));
+    }
+    let (v, w) = (vb / sum, vc / sum);
+    (a + (ab * v) + (ac * w), Region::Interior([0, 1, 2]))
+}
+
+/// Finds the point on the surface of a graph closest to the given point.
+///
+/// If a hierarchy is given, then faces are visited nearest first and faces
+/// with bounds farther than the closest point found so far are skipped.
+pub(in crate::graph) fn closest_point<G>(
+    graph: &MeshGraph<G>,
+    bvh: Option<&Bvh<FaceKey, VertexPosition<G>>>,
+    point: VertexPosition<G>,
+) -> Result<Option<ClosestPoint<VertexPosition<G>>>, GraphError>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+{
+    Ok(
+        nearest(graph, bvh, point)?.map(|(triangulation, distance, position, region)| {
+            let feature = match region {
+                Region::Corner(index) => Feature::Vertex(triangulation.ring[index].1),
+                Region::Segment(a, b) => match triangulation.arc(a, b) {
+                    Some(index) => Feature::Edge(
+                        graph
+                            .arc(triangulation.ring[index].0)
+                            .expect_consistent()
+                            .edge()
+                            .key(),
+                    ),
+                    None => Feature::Face(triangulation.face),
+                },
+                Region::Interior(_) => Feature::Face(triangulation.face),
+            };
+            ClosestPoint {
+                face: triangulation.face,
+                feature,
+                position,
+                distance: Real::sqrt(distance),
+            }
+        }),
+    )
+}
+
+/// Computes the signed distance from the surface of a graph to the given
+/// point.
+///
+/// The distance is negative if the point is inside of the surface.
+pub(in crate::graph) fn signed_distance<G>(
+    graph: &MeshGraph<G>,
+    bvh: Option<&Bvh<FaceKey, VertexPosition<G>>>,
+    point: VertexPosition<G>,
+) -> Result<Scalar<VertexPosition<G>>, GraphError>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+{
+    if graph.arcs().any(|arc| arc.is_boundary_arc()) {
+        return Err(GraphError::TopologyMalformed);
+    }
+    let (triangulation, distance, position, region) =
+        nearest(graph, bvh, point)?.ok_or(GraphError::TopologyNotFound)?;
+    let distance = Real::sqrt(distance);
+    let normal = match region {
+        Region::Interior(triangle) => triangulation.normal(triangle),
+        Region::Segment(a, b) => match triangulation.arc(a, b) {
+            Some(index) => {
+                // The pseudo-normal of an edge is the sum of the normals of the
+                // triangles on either side of it.
+                let opposite = graph
+                    .arc(triangulation.ring[index].0)
+                    .expect_consistent()
+                    .into_opposite_arc();
+                let adjacent = Triangulation::from_face(opposite.face().expect_consistent());
+                let other = adjacent
+                    .ring
+                    .iter()
+                    .position(|(key, _, _)| *key == opposite.key())
+                    .expect_consistent();
+                triangulation.segment_normal(a, b)
+                    + adjacent.segment_normal(other, (other + 1) % adjacent.ring.len())
+            }
+            // The pseudo-normal of a diagonal is the sum of the normals of the
+            // triangles on either side of it, both of which belong to the
+            // face.
+            None => triangulation.segment_normal(a, b),
+        },
+        Region::Corner(index) => vertex_normal(graph, triangulation.ring[index].1),
+    };
+    Ok(if (point - position).dot(normal) < Zero::zero() {
+        -distance
+    }
+    else {
+        distance
+    })
+}
+
+/// Finds the triangulation of the face nearest to the given point and the
+/// squared distance, closest point, and region of that triangulation.
+#[allow(clippy::type_complexity)]
+fn nearest<G>(
+    graph: &MeshGraph<G>,
+    bvh: Option<&Bvh<FaceKey, VertexPosition<G>>>,
+    point: VertexPosition<G>,
+) -> Result<
+    Option<(
+        Triangulation<VertexPosition<G>>,
+        Scalar<VertexPosition<G>>,
+        VertexPosition<G>,
+        Region,
+    )>,
+    GraphError,
+>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+{
+    let candidate = |face: FaceView<&MeshGraph<G>>| {
+        let triangulation = Triangulation::from_face(face);
+        let (distance, position, region) = triangulation.closest(point);
+        (distance, (triangulation, distance, position, region))
+    };
+    if let Some(bvh) = bvh {
+        let mut is_missing = false;
+        let nearest = bvh.nearest_with(point, |key| match graph.face(*key) {
+            Some(face) => Some(candidate(face)),
+            None => {
+                is_missing = true;
+                None
+            }
+        });
+        if is_missing {
+            Err(GraphError::TopologyNotFound)
+        }
+        else {
+            Ok(nearest)
+        }
+    }
+    else {
+        Ok(graph
+            .faces()
+            .map(candidate)
+            .fold(None, |nearest, (distance, output)| match nearest {
+                Some((nearest, nearest_output)) if nearest <= distance => {
+                    Some((nearest, nearest_output))
+                }
+                _ => Some((distance, output)),
+            })
+            .map(|(_, output)| output))
+    }
+}
+
+/// Computes the angle-weighted pseudo-normal of a vertex.
+///
+/// The normals of the triangles of each adjacent triangulation that include
+/// the vertex are weighted by the angles of those triangles at the vertex.
+fn vertex_normal<G>(graph: &MeshGraph<G>, key: VertexKey) -> Vector<VertexPosition<G>>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+{
+    let vertex = graph.vertex(key).expect_consistent();
+    vertex
+        .outgoing_arcs()
+        .flat_map(|arc| {
+            let key = arc.key();
+            arc.into_face().map(|face| (key, face))
+        })
+        .fold(Zero::zero(), |normal, (arc, face)| {
+            let triangulation = Triangulation::from_face(face);
+            let index = triangulation
+                .ring
+                .iter()
+                .position(|(key, _, _)| *key == arc)
+                .expect_consistent();
+            normal + triangulation.corner_normal(index)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Point3;
+
+    use crate::graph::{Feature, MeshGraph};
+    use crate::prelude::*;
+    use crate::primitive::cube::Cube;
+    use crate::primitive::generate::Position;
+    use crate::primitive::NGon;
+
+    type E3 = Point3<f64>;
+
+    #[test]
+    fn closest_feature() {
+        let graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+        let bvh = graph.bvh();
+
+        let closest = graph.closest_point(E3::new(0.25, 0.125, 2.0)).unwrap();
+        assert!(matches!(closest.feature, Feature::Face(key) if key == closest.face));
+        assert!((closest.position - E3::new(0.25, 0.125, 0.5)).norm() < 1e-9);
+        assert!((closest.distance - 1.5).abs() < 1e-9);
+
+        let closest = graph.closest_point(E3::new(1.0, 1.0, 0.0)).unwrap();
+        assert!(matches!(closest.feature, Feature::Edge(_)));
+        assert!((closest.position - E3::new(0.5, 0.5, 0.0)).norm() < 1e-9);
+
+        let closest = graph
+            .closest_point_by_bvh(&bvh, E3::new(1.0, 1.0, 1.0))
+            .unwrap()
+            .unwrap();
+        assert!(matches!(closest.feature, Feature::Vertex(_)));
+        assert!((closest.position - E3::new(0.5, 0.5, 0.5)).norm() < 1e-9);
+    }
+
+    #[test]
+    fn closest_point_on_concave_face() {
+        // An L-shaped face with a notch that contains the centroid of its
+        // vertices.
+        let graph = MeshGraph::<E3>::from_raw_buffers(
+            vec![NGon([0usize, 1, 2, 3, 4, 5])],
+            vec![
+                (0.0, 0.0, 0.0),
+                (4.0, 0.0, 0.0),
+                (4.0, 1.0, 0.0),
+                (1.0, 1.0, 0.0),
+                (1.0, 3.0, 0.0),
+                (0.0, 3.0, 0.0),
+            ],
+        )
+        .unwrap();
+
+        // The closest point to a point above the notch lies on an edge of
+        // the notch rather than within it.
+        let closest = graph.closest_point(E3::new(1.3, 1.2, 1.0)).unwrap();
+        assert!(matches!(closest.feature, Feature::Edge(_)));
+        assert!((closest.position - E3::new(1.3, 1.0, 0.0)).norm() < 1e-9);
+        assert!((closest.distance - 1.04f64.sqrt()).abs() < 1e-9);
+
+        let closest = graph.closest_point(E3::new(0.5, 2.0, 1.0)).unwrap();
+        assert!(matches!(closest.feature, Feature::Face(_)));
+        assert!((closest.position - E3::new(0.5, 2.0, 0.0)).norm() < 1e-9);
+    }
+
+    #[test]
+    fn signed_distance() {
+        let graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+        let bvh = graph.bvh();
+
+        for (point, expected) in &[
+            (E3::new(0.0, 0.0, 0.0), -0.5),
+            (E3::new(0.0, 0.0, 2.0), 1.5),
+            (E3::new(0.4, 0.4, 0.4), -0.1),
+            (E3::new(1.0, 1.0, 0.0), 0.5f64.sqrt()),
+            (E3::new(1.0, 1.0, 1.0), 0.75f64.sqrt()),
+        ] {
+            let distance = graph.signed_distance(*point).unwrap();
+            assert!((distance - expected).abs() < 1e-9);
+            let distance = graph.signed_distance_by_bvh(&bvh, *point).unwrap();
+            assert!((distance - expected).abs() < 1e-9);
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use decorum::Real;
 use derivative::Derivative;
 use fool::BoolExt as _;
 use smallvec::SmallVec;
@@ -7,23 +8,26 @@ use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 use std::mem;
 use std::ops::{Deref, DerefMut};
+use theon::ops::Dot;
 use theon::query::{Intersection, Line, Plane};
 use theon::space::{EuclideanSpace, FiniteDimensional, Scalar, Vector};
 use theon::{AsPosition, AsPositionMut};
 use typenum::U3;
 
 use crate::entity::borrow::{Reborrow, ReborrowInto, ReborrowMut};
+use crate::entity::dijkstra;
 use crate::entity::storage::prelude::*;
 use crate::entity::storage::{AsStorage, AsStorageMut, HashStorage, IncrementalKeyer, Key};
 use crate::entity::traverse::{Adjacency, Breadth, Depth, Trace, TraceFirst, Traversal};
 use crate::entity::view::{Bind, ClosedView, Orphan, Rebind, Unbind, View};
 use crate::entity::{Entity, Payload};
+use crate::geometry::Metric;
 use crate::graph::data::{Data, GraphData, Parametric};
 use crate::graph::edge::{Arc, ArcKey, ArcOrphan, ArcView, Edge};
-use crate::graph::geometry::{FaceCentroid, FaceNormal, FacePlane, VertexPosition};
+use crate::graph::geometry::{FaceArea, FaceCentroid, FaceNormal, FacePlane, VertexPosition};
 use crate::graph::mutation::face::{
     self, FaceBridgeCache, FaceExtrudeCache, FaceInsertCache, FacePokeCache, FaceRemoveCache,
-    FaceSplitCache,
+    FaceReverseCache, FaceSplitCache,
 };
 use crate::graph::mutation::{self, Consistent, Immediate, Mutable};
 use crate::graph::path::Path;
@@ -276,6 +280,18 @@ where
         G::centroid(self.to_ref()).expect_consistent()
     }
 
+    /// Gets the area of the face.
+    ///
+    /// The area is computed from a fan of triangles about the centroid of the
+    /// face, and so non-planar faces are supported.
+    pub fn area(&self) -> Scalar<VertexPosition<G>>
+    where
+        G: FaceArea,
+        G::Vertex: AsPosition,
+    {
+        G::area(self.to_ref()).expect_consistent()
+    }
+
     pub fn normal(&self) -> Result<Vector<VertexPosition<G>>, GraphError>
     where
         G: FaceNormal,
@@ -292,6 +308,85 @@ where
     {
         G::plane(self.to_ref())
     }
+
+    /// Selects a region of faces that are reachable from the face without
+    /// crossing an edge with a dihedral angle that exceeds the given angle.
+    ///
+    /// Here, the dihedral angle is the angle between the normals of adjacent
+    /// faces, so coplanar faces have a dihedral angle of zero. The angle is
+    /// expressed in radians. Faces with degenerate geometry (for which a
+    /// normal cannot be computed) are never selected, except for the face
+    /// itself.
+    ///
+    /// See [`select_with`].
+    ///
+    /// # Examples
+    ///
+    /// Selecting the faces of one side of a triangulated cube:
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::Point3;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::index::HashIndexer;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::cube::Cube;
+    /// use plexus::primitive::generate::Position;
+    /// use std::f64::consts::FRAC_PI_4;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// let mut graph: MeshGraph<E3> = Cube::new()
+    ///     .polygons::<Position<E3>>()
+    ///     .collect_with_indexer(HashIndexer::default())
+    ///     .unwrap();
+    /// graph.triangulate();
+    ///
+    /// let face = graph.faces().nth(0).unwrap();
+    /// let keys = face.select_by_dihedral_angle(FRAC_PI_4);
+    /// assert_eq!(2, keys.len());
+    /// ```
+    ///
+    /// [`select_with`]: crate::graph::FaceView::select_with
+    pub fn select_by_dihedral_angle<T>(&self, angle: T) -> HashSet<FaceKey>
+    where
+        G: FaceNormal,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+        T: Into<Scalar<VertexPosition<G>>>,
+    {
+        self.select_by_dihedral_angle_with(angle, |_, _| true)
+    }
+
+    /// Selects a region of faces that are reachable from the face without
+    /// crossing an edge with a dihedral angle that exceeds the given angle or
+    /// for which the given predicate returns `false`.
+    ///
+    /// The predicate receives the selected face from which the selection grows
+    /// and the adjacent face into which it grows (in that order).
+    ///
+    /// See [`select_by_dihedral_angle`] and [`select_with`].
+    ///
+    /// [`select_by_dihedral_angle`]: crate::graph::FaceView::select_by_dihedral_angle
+    /// [`select_with`]: crate::graph::FaceView::select_with
+    pub fn select_by_dihedral_angle_with<T, F>(&self, angle: T, mut f: F) -> HashSet<FaceKey>
+    where
+        G: FaceNormal,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+        T: Into<Scalar<VertexPosition<G>>>,
+        F: FnMut(FaceView<&M>, FaceView<&M>) -> bool,
+    {
+        let threshold = Real::cos(angle.into());
+        self.select_with(move |from, to| match (from.normal(), to.normal()) {
+            (Ok(a), Ok(b)) => (a.dot(b) >= threshold) && f(from, to),
+            _ => false,
+        })
+    }
 }
 
 impl<B, M, G> FaceView<B>
@@ -496,6 +591,142 @@ where
     pub fn traverse_by_depth(&self) -> impl Clone + Iterator<Item = FaceView<&B::Target>> {
         Traversal::<_, _, Depth>::from(self.to_ref())
     }
+
+    /// Selects a region of faces that are reachable from the face by breadth,
+    /// only moving into adjacent faces for which the given predicate returns
+    /// `true`.
+    ///
+    /// The predicate receives the selected face from which the selection grows
+    /// and the adjacent face into which it grows (in that order). The face is
+    /// always selected. Faces rejected by the predicate are not selected unless
+    /// they are reachable via some other selected face that is accepted.
+    ///
+    /// # Examples
+    ///
+    /// Selecting quadrilateral faces:
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::Point3;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::index::HashIndexer;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::cube::Cube;
+    /// use plexus::primitive::generate::Position;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// let graph: MeshGraph<E3> = Cube::new()
+    ///     .polygons::<Position<E3>>()
+    ///     .collect_with_indexer(HashIndexer::default())
+    ///     .unwrap();
+    ///
+    /// let face = graph.faces().nth(0).unwrap();
+    /// let keys = face.select_with(|_, to| to.arity() == 4);
+    /// assert_eq!(6, keys.len());
+    /// ```
+    pub fn select_with<F>(&self, mut f: F) -> HashSet<FaceKey>
+    where
+        F: FnMut(FaceView<&B::Target>, FaceView<&B::Target>) -> bool,
+    {
+        Traversal::<_, _, Breadth, _>::with_filter(
+            self.to_ref(),
+            |from: &FaceView<&B::Target>, to: &FaceView<&B::Target>| f(*from, *to),
+        )
+        .keys()
+        .collect()
+    }
+
+    /// Gets the shortest path of faces to the given face.
+    ///
+    /// Faces are adjacent if they share an edge and the path moves between
+    /// faces across such edges. The logical metric is used, which counts the
+    /// number of moves. See [`shortest_path_with`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the destination face is not found or is
+    /// unreachable.
+    ///
+    /// [`shortest_path_with`]: crate::graph::FaceView::shortest_path_with
+    pub fn shortest_path(&self, key: FaceKey) -> Result<Vec<FaceView<&B::Target>>, GraphError> {
+        self.shortest_path_with(key, |_, _| 1usize)
+    }
+
+    /// Gets the shortest path of faces to the given face using the given
+    /// metric.
+    ///
+    /// The metric receives the face from which the path moves and the
+    /// adjacent face into which it moves (in that order). The output is
+    /// ordered from this face to the destination face, both of which are
+    /// included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the destination face is not found or is unreachable
+    /// or if the metric is negative.
+    ///
+    /// # Examples
+    ///
+    /// Finding a strip of faces between two faces using the distance between
+    /// centroids:
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// # extern crate theon;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::Point3;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::generate::Position;
+    /// use plexus::primitive::sphere::UvSphere;
+    /// use theon::space::InnerSpace;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// let graph: MeshGraph<E3> = UvSphere::new(16, 8).polygons::<Position<E3>>().collect();
+    /// let from = graph.faces().nth(0).unwrap();
+    /// let to = graph.faces().nth(64).unwrap();
+    /// let faces = from
+    ///     .shortest_path_with(to.key(), |from, to| {
+    ///         (to.centroid() - from.centroid()).magnitude()
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(from.key(), faces.first().unwrap().key());
+    /// assert_eq!(to.key(), faces.last().unwrap().key());
+    /// ```
+    pub fn shortest_path_with<Q, F>(
+        &self,
+        mut key: FaceKey,
+        f: F,
+    ) -> Result<Vec<FaceView<&B::Target>>, GraphError>
+    where
+        Q: Copy + Metric,
+        F: Fn(FaceView<&B::Target>, FaceView<&B::Target>) -> Q,
+    {
+        let (storage, from) = self.to_ref().unbind();
+        let metrics = dijkstra::search_with(storage, Some(from), Some(key), f, |_| Q::zero())?;
+        if !metrics.contains_key(&key) {
+            return Err(GraphError::TopologyUnreachable);
+        }
+        let mut keys = vec![key];
+        while let Some((Some(previous), _)) = metrics.get(&key) {
+            key = *previous;
+            keys.push(key);
+        }
+        Ok(keys
+            .into_iter()
+            .rev()
+            .map(|key| Bind::bind(storage, key).expect_consistent())
+            .collect())
+    }
 }
 
 impl<'a, M, G> FaceView<&'a mut M>
@@ -858,6 +1089,63 @@ where
             .expect_consistent()
     }
 
+    /// Reverses the winding of an isolated face in place.
+    ///
+    /// The vertices of the face are visited in the opposite order and the
+    /// normal of the face is negated. Adjacent faces share edges and the arcs
+    /// of an edge belong to opposing faces, so the winding of adjacent faces
+    /// always agrees and a face within a mesh cannot be reversed alone. Only a
+    /// face that is isolated from all other faces can be reversed. To reverse
+    /// a face along with its connected faces, see [`MeshGraph::orient`].
+    ///
+    /// Returns the reversed face.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the face shares any arcs or vertices with another
+    /// face.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use nalgebra::Point2;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::Trigon;
+    ///
+    /// let mut graph = MeshGraph::<Point2<f64>>::from_raw_buffers(
+    ///     vec![Trigon::new(0usize, 1, 2)],
+    ///     vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)],
+    /// )
+    /// .unwrap();
+    /// let key = graph.faces().nth(0).unwrap().key();
+    /// let arcs = graph
+    ///     .face(key)
+    ///     .unwrap()
+    ///     .adjacent_arcs()
+    ///     .map(|arc| (arc.source_vertex().key(), arc.destination_vertex().key()))
+    ///     .collect::<Vec<_>>();
+    /// let face = graph.face_mut(key).unwrap().reverse_isolated().unwrap();
+    ///
+    /// // The face traverses each of its arcs in the opposite direction.
+    /// assert!(face.adjacent_arcs().all(|arc| {
+    ///     arcs.contains(&(arc.destination_vertex().key(), arc.source_vertex().key()))
+    /// }));
+    /// ```
+    ///
+    /// [`MeshGraph::orient`]: crate::graph::MeshGraph::orient
+    pub fn reverse_isolated(self) -> Result<Self, GraphError> {
+        let cache = FaceReverseCache::from_face(self.to_ref())?;
+        let (storage, abc) = self.unbind();
+        Mutation::take(storage)
+            .bypass_or_commit_with(|mutation| face::reverse(mutation, cache))
+            .map(|(storage, _)| Bind::bind(storage, abc).expect_consistent())
+            .map_err(|(_, error)| error)
+    }
+
     /// Removes the face.
     ///
     /// Returns the remaining ring of the face if it is not entirely disjoint, otherwise `None`.
@@ -1751,14 +2039,15 @@ where
 mod tests {
     use decorum::R64;
     use nalgebra::{Point2, Point3};
+    use num::Zero;
 
-    use crate::graph::MeshGraph;
+    use crate::graph::{GraphError, MeshGraph};
     use crate::index::HashIndexer;
     use crate::prelude::*;
     use crate::primitive::cube::Cube;
     use crate::primitive::generate::Position;
     use crate::primitive::sphere::UvSphere;
-    use crate::primitive::Tetragon;
+    use crate::primitive::{Tetragon, Trigon};
 
     type E2 = Point2<R64>;
     type E3 = Point3<R64>;
@@ -1861,6 +2150,46 @@ mod tests {
         assert_eq!(9, graph.face_count());
     }
 
+    #[test]
+    fn reverse_isolated_face() {
+        let mut graph = MeshGraph::<E3>::from_raw_buffers(
+            vec![Trigon::new(0usize, 1, 2)],
+            vec![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0)],
+        )
+        .unwrap();
+        let key = graph.faces().nth(0).unwrap().key();
+        assert!(graph.face(key).unwrap().normal().unwrap().z > Zero::zero());
+
+        // The normal of the face should be negated.
+        let face = graph
+            .face_mut(key)
+            .unwrap()
+            .reverse_isolated()
+            .unwrap()
+            .into_ref();
+        assert!(face.normal().unwrap().z < Zero::zero());
+        assert!(graph.validate().is_valid());
+    }
+
+    #[test]
+    fn reverse_face_with_adjacent_faces_error() {
+        let mut graph: MeshGraph<E3> = Cube::new()
+            .polygons::<Position<E3>>()
+            .collect_with_indexer(HashIndexer::default())
+            .unwrap();
+        let key = graph.faces().nth(0).unwrap().key();
+
+        assert_eq!(
+            GraphError::TopologyConflict,
+            graph
+                .face_mut(key)
+                .unwrap()
+                .reverse_isolated()
+                .err()
+                .unwrap()
+        );
+    }
+
     #[test]
     fn merge_faces() {
         // Construct a graph with two connected quadrilaterals.
@@ -1891,6 +2220,140 @@ mod tests {
         assert_eq!(6, graph.faces().nth(0).unwrap().arity());
     }
 
+    #[test]
+    fn select_faces_by_dihedral_angle() {
+        use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+        let mut graph: MeshGraph<E3> = Cube::new()
+            .polygons::<Position<E3>>() // 6 quadrilaterals, 24 vertices.
+            .collect_with_indexer(HashIndexer::default())
+            .unwrap();
+        graph.triangulate();
+        let face = graph.faces().nth(0).unwrap();
+
+        // Only the two coplanar triangles on one side of the cube should be
+        // selected.
+        assert_eq!(2, face.select_by_dihedral_angle(FRAC_PI_4).len());
+        // All faces meet at right angles, so every face should be selected.
+        assert_eq!(
+            12,
+            face.select_by_dihedral_angle(FRAC_PI_2 + FRAC_PI_4).len()
+        );
+        // A predicate that rejects all adjacent faces should only select the
+        // initiating face.
+        let keys = face.select_by_dihedral_angle_with(FRAC_PI_2 + FRAC_PI_4, |_, _| false);
+        assert_eq!(1, keys.len());
+        assert!(keys.contains(&face.key()));
+    }
+
+    #[test]
+    fn select_faces_with_predicate() {
+        let graph: MeshGraph<E3> = UvSphere::new(4, 4)
+            .polygons::<Position<E3>>()
+            .collect_with_indexer(HashIndexer::default())
+            .unwrap();
+        let face = graph.faces().find(|face| face.arity() == 3).unwrap();
+
+        // Selection should not grow beyond the triangles that form a pole of
+        // the sphere.
+        let keys = face.select_with(|_, to| to.arity() == 3);
+        assert_eq!(4, keys.len());
+        assert!(keys
+            .iter()
+            .all(|key| graph.face(*key).unwrap().arity() == 3));
+    }
+
+    #[test]
+    fn flatten_face() {
+        let mut graph = MeshGraph::<Point3<f64>>::from_raw_buffers_with_arity(
+            vec![0u32, 1, 2, 3],
+            vec![
+                (0.0, 0.0, 0.0),
+                (1.0, 0.0, 0.1),
+                (1.0, 1.0, 0.0),
+                (0.0, 1.0, 0.1),
+            ],
+            4,
+        )
+        .unwrap();
+        let key = graph.faces().nth(0).unwrap().key();
+        graph.face_mut(key).unwrap().flatten().unwrap();
+
+        let face = graph.face(key).unwrap();
+        let plane = face.plane().unwrap();
+        for vertex in face.adjacent_vertices() {
+            let distance = (*vertex.position() - plane.origin).dot(plane.normal.get());
+            assert!(distance.abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn concave_face_normal() {
+        // A concave "C" shaped face. Its centroid lies outside of the face in
+        // its opening.
+        let graph = MeshGraph::<Point3<f64>>::from_raw_buffers_with_arity(
+            vec![0u32, 1, 2, 3, 4, 5, 6, 7],
+            vec![
+                (0.0, 0.0, 0.0),
+                (10.0, 0.0, 0.0),
+                (10.0, 1.0, 0.0),
+                (1.0, 1.0, 0.0),
+                (1.0, 9.0, 0.0),
+                (10.0, 9.0, 0.0),
+                (10.0, 10.0, 0.0),
+                (0.0, 10.0, 0.0),
+            ],
+            8,
+        )
+        .unwrap();
+        let normal = graph.faces().nth(0).unwrap().normal().unwrap();
+
+        assert!((normal.z - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn degenerate_face_normal() {
+        let graph = MeshGraph::<Point3<f64>>::from_raw_buffers(
+            vec![Trigon::new(0u32, 1, 2)],
+            vec![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (2.0, 0.0, 0.0)],
+        )
+        .unwrap();
+
+        assert_eq!(
+            Err(GraphError::Geometry),
+            graph.faces().nth(0).unwrap().normal()
+        );
+    }
+
+    #[test]
+    fn shortest_face_path() {
+        let graph: MeshGraph<E3> = Cube::new()
+            .polygons::<Position<E3>>()
+            .collect_with_indexer(HashIndexer::default())
+            .unwrap();
+        let face = graph.faces().nth(0).unwrap();
+        // The opposite face is the only face that is neither the face nor
+        // adjacent to it.
+        let opposite = graph
+            .faces()
+            .find(|opposite| {
+                opposite.key() != face.key()
+                    && face
+                        .adjacent_faces()
+                        .all(|adjacent| adjacent.key() != opposite.key())
+            })
+            .unwrap();
+        let faces = face.shortest_path(opposite.key()).unwrap();
+
+        // Opposite faces of a cube are separated by exactly one face.
+        assert_eq!(3, faces.len());
+        assert_eq!(face.key(), faces[0].key());
+        assert_eq!(opposite.key(), faces[2].key());
+        assert!(faces[1]
+            .adjacent_faces()
+            .any(|adjacent| adjacent.key() == face.key()));
+    }
+
     #[test]
     fn poke_face() {
         let mut graph: MeshGraph<E3> = Cube::new()
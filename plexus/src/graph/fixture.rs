@@ -0,0 +1,70 @@
+//! Graphs shared by tests.
+
+use nalgebra::Point3;
+
+use crate::graph::{MeshGraph, RepairReport};
+use crate::primitive::{Tetragon, Trigon};
+
+type E3 = Point3<f64>;
+
+/// Gets a flat grid of four quadrilaterals.
+///
+/// The nine vertices of the grid lie on the integer coordinates from zero to
+/// two in the `xy` plane and are indexed by row.
+pub fn grid() -> MeshGraph<E3> {
+    grid_with(|_| 0.0)
+}
+
+/// Gets a grid of four quadrilaterals with the given heights.
+///
+/// The function maps the index of each vertex to its `z` coordinate.
+pub fn grid_with<F>(f: F) -> MeshGraph<E3>
+where
+    F: Fn(usize) -> f64,
+{
+    MeshGraph::<E3>::from_raw_buffers(
+        vec![
+            Tetragon::new(0usize, 1, 4, 3),
+            Tetragon::new(1, 2, 5, 4),
+            Tetragon::new(3, 4, 7, 6),
+            Tetragon::new(4, 5, 8, 7),
+        ],
+        (0..9)
+            .map(|index| ((index % 3) as f64, (index / 3) as f64, f(index)))
+            .collect::<Vec<_>>(),
+    )
+    .unwrap()
+}
+
+/// Gets the triangles of a triangulated Möbius strip.
+///
+/// The winding of the triangles conflicts along an edge.
+pub fn mobius_faces() -> Vec<Trigon<usize>> {
+    vec![
+        Trigon::new(0usize, 1, 4),
+        Trigon::new(1, 5, 4),
+        Trigon::new(1, 2, 5),
+        Trigon::new(0, 4, 3),
+        Trigon::new(2, 3, 0),
+        Trigon::new(2, 0, 5),
+    ]
+}
+
+/// Gets a triangulated Möbius strip and the report of its repair.
+///
+/// The edge along which the winding conflicts is split when the graph is
+/// constructed.
+pub fn mobius() -> (MeshGraph<E3>, RepairReport) {
+    MeshGraph::<E3>::from_raw_buffers_with_repair(
+        mobius_faces(),
+        vec![
+            (1.0, 0.0, 0.0),
+            (-0.5, 0.9, 0.0),
+            (-0.5, -0.9, 0.0),
+            (2.0, 0.0, 0.5),
+            (-1.0, 1.7, -0.5),
+            (-1.0, -1.7, 0.5),
+        ],
+    )
+    .unwrap()
+}
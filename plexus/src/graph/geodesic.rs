@@ -0,0 +1,417 @@
+//! Geodesic distance fields.
+//!
+//! Distances are computed using the fast marching method, which propagates a
+//! front over the surface of a graph in order of increasing distance. Unlike
+//! edge-graph searches (see `entity::dijkstra`), the front may cross faces and
+//! does not zig-zag along edges. Obtuse corners are split into acute triangles
+//! by unfolding adjacent triangles (Kimmel and Sethian), so that the front
+//! only updates a vertex from vertices that are reached before it.
+
+use decorum::Real;
+use num::{One, Zero};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use theon::ops::Dot;
+use theon::space::{EuclideanSpace, InnerSpace, Scalar};
+use theon::AsPosition;
+
+use crate::graph::data::GraphData;
+use crate::graph::edge::ArcView;
+use crate::graph::geometry::VertexPosition;
+use crate::graph::vertex::VertexKey;
+use crate::graph::{GraphError, MeshGraph, OptionExt as _};
+
+/// Maximum number of triangles unfolded to split an obtuse corner.
+const UNFOLDINGS: usize = 16;
+
+/// A vertex on the front ordered by its (reversed) tentative distance.
+struct Front<T>(T, VertexKey);
+
+impl<T> Eq for Front<T> where T: PartialOrd {}
+
+impl<T> Ord for Front<T>
+where
+    T: PartialOrd,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reverse the ordering so that `BinaryHeap` yields the least distance.
+        other.0.partial_cmp(&self.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<T> PartialEq for Front<T>
+where
+    T: PartialOrd,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> PartialOrd for Front<T>
+where
+    T: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Computes the geodesic distance from the nearest source to each vertex that
+/// is reachable from the sources.
+pub(in crate::graph) fn distances<G>(
+    graph: &MeshGraph<G>,
+    sources: &[VertexKey],
+) -> Result<HashMap<VertexKey, Scalar<VertexPosition<G>>>, GraphError>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace,
+{
+    let mut distances = HashMap::with_capacity(graph.vertex_count());
+    let mut fixed = HashSet::with_capacity(graph.vertex_count());
+    let mut front = BinaryHeap::new();
+    for key in sources {
+        graph.vertex(*key).ok_or(GraphError::TopologyNotFound)?;
+        distances.insert(*key, Zero::zero());
+        front.push(Front(Zero::zero(), *key));
+    }
+    while let Some(Front(_, key)) = front.pop() {
+        if !fixed.insert(key) {
+            continue;
+        }
+        let vertex = graph.vertex(key).expect_consistent();
+        for adjacent in vertex.adjacent_vertices() {
+            if fixed.contains(&adjacent.key()) {
+                continue;
+            }
+            let c = *adjacent.position();
+            let mut distance = distances.get(&adjacent.key()).cloned();
+            let mut relax = |candidate: Scalar<VertexPosition<G>>| {
+                if distance.map_or(true, |distance| candidate < distance) {
+                    distance = Some(candidate);
+                }
+            };
+            for arc in adjacent.outgoing_arcs() {
+                let x = arc.destination_vertex();
+                let dx = fixed.get(&x.key()).and(distances.get(&x.key())).cloned();
+                if let Some(dx) = dx {
+                    relax(dx + (c - *x.position()).magnitude());
+                }
+                if arc.is_boundary_arc() {
+                    continue;
+                }
+                // Unfold the corner of the face at the adjacent vertex into a
+                // triangle and update from the edge opposite the corner. If
+                // the corner is obtuse, then it is first split into two acute
+                // triangles by a virtual edge.
+                let y = arc.previous_arc().source_vertex();
+                let dy = fixed.get(&y.key()).and(distances.get(&y.key())).cloned();
+                let (x, y) = (*x.position(), *y.position());
+                let w = if (x - c).dot(y - c) < Zero::zero() {
+                    split(arc).and_then(|(key, w)| {
+                        fixed.get(&key).and(distances.get(&key)).map(|dw| (w, *dw))
+                    })
+                }
+                else {
+                    None
+                };
+                if let Some((w, dw)) = w {
+                    if let Some(dx) = dx {
+                        if let Some(candidate) = unfold(x, dx, w, dw, c) {
+                            relax(candidate);
+                        }
+                    }
+                    if let Some(dy) = dy {
+                        if let Some(candidate) = unfold(w, dw, y, dy, c) {
+                            relax(candidate);
+                        }
+                    }
+                }
+                else if let (Some(dx), Some(dy)) = (dx, dy) {
+                    if let Some(candidate) = unfold(x, dx, y, dy, c) {
+                        relax(candidate);
+                    }
+                }
+            }
+            if let Some(distance) = distance {
+                if distances
+                    .get(&adjacent.key())
+                    .map_or(true, |previous| distance < *previous)
+                {
+                    distances.insert(adjacent.key(), distance);
+                    front.push(Front(distance, adjacent.key()));
+                }
+            }
+        }
+    }
+    Ok(distances)
+}
+
+/// Finds a vertex with which to split the obtuse corner of a triangle.
+///
+/// The given arc leaves the corner. The triangles opposite the corner are
+/// unfolded into its plane until a vertex is found within the section bounded
+/// by the perpendiculars to the edges of the corner (Kimmel and Sethian). The
+/// virtual edge from the corner to this vertex splits the corner into two acute
+/// angles. Returns the key and unfolded position of the vertex or `None` if no
+/// such vertex is found, such as when unfolding reaches a boundary or a face
+/// that is not a triangle.
+fn split<G>(arc: ArcView<&MeshGraph<G>>) -> Option<(VertexKey, VertexPosition<G>)>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace,
+{
+    let zero = Scalar::<VertexPosition<G>>::zero();
+    let c = *arc.source_vertex().position();
+    let mut edge = arc.into_next_arc();
+    let (x, y) = (
+        *edge.source_vertex().position(),
+        *edge.destination_vertex().position(),
+    );
+    if edge.destination_vertex().key() != arc.previous_arc().source_vertex().key() {
+        // The face is not a triangle.
+        return None;
+    }
+    // The unfolded positions of the endpoints of the edge across which the
+    // next triangle is unfolded and of the opposite vertex of the previous
+    // triangle.
+    let (mut p, mut q, mut opposite) = (x, y, c);
+    for _ in 0..UNFOLDINGS {
+        let next = edge.into_opposite_arc();
+        if next.is_boundary_arc() {
+            return None;
+        }
+        let (a, b) = (next.destination_vertex(), next.source_vertex());
+        let next = next.into_next_arc();
+        let w = next.destination_vertex();
+        if next.next_arc().destination_vertex().key() != b.key() {
+            // The face is not a triangle.
+            return None;
+        }
+        let position = place(
+            (p, (*w.position() - *a.position()).magnitude()),
+            (q, (*w.position() - *b.position()).magnitude()),
+            opposite,
+        )?;
+        let direction = position - c;
+        let (dx, dy) = (direction.dot(x - c), direction.dot(y - c));
+        if dx > zero && dy > zero {
+            return Some((w.key(), position));
+        }
+        // Continue across the edge of the unfolded triangle on the side of the
+        // section.
+        if dy <= zero {
+            edge = next.into_next_arc();
+            opposite = p;
+            p = position;
+        }
+        else {
+            edge = next;
+            opposite = q;
+            q = position;
+        }
+    }
+    None
+}
+
+/// Places a point in the plane of a triangle at the given distances from the
+/// endpoints of one of its edges on the side of that edge opposite the
+/// triangle.
+///
+/// The edge is given by its endpoints and the triangle is given by the vertex
+/// opposite the edge. Returns `None` if the triangle is degenerate.
+fn place<S>(a: (S, Scalar<S>), b: (S, Scalar<S>), c: S) -> Option<S>
+where
+    S: EuclideanSpace,
+{
+    let zero = Scalar::<S>::zero();
+    let two = Scalar::<S>::one() + Scalar::<S>::one();
+    let ((a, da), (b, db)) = (a, b);
+    let ab = (b - a).magnitude();
+    if ab <= zero {
+        return None;
+    }
+    let u = (b - a) * (Scalar::<S>::one() / ab);
+    let v = (c - a) - (u * u.dot(c - a));
+    let v = v.normalize()?;
+    let x = ((da * da) - (db * db) + (ab * ab)) / (two * ab);
+    let y = Real::sqrt(Real::max((da * da) - (x * x), zero));
+    Some(a + (u * x) - (v * y))
+}
+
+/// Computes the distance at `c` from a planar wavefront that reaches `a` and
+/// `b` at the given distances.
+///
+/// The triangle $abc$ is unfolded into the plane and a virtual source is
+/// placed on the side of $ab$ opposite $c$. Returns `None` if no such source
+/// exists or if the path from the source to $c$ does not cross $ab$, in which
+/// case the distance is given by the edges of the triangle.
+fn unfold<S>(a: S, da: Scalar<S>, b: S, db: Scalar<S>, c: S) -> Option<Scalar<S>>
+where
+    S: EuclideanSpace,
+{
+    let zero = Scalar::<S>::zero();
+    let two = Scalar::<S>::one() + Scalar::<S>::one();
+    let ab = (b - a).magnitude();
+    let ac = (c - a).magnitude();
+    let bc = (c - b).magnitude();
+    if ab <= zero {
+        return None;
+    }
+    // Place `a` at the origin and `b` on the positive horizontal axis.
+    let cx = ((ac * ac) + (ab * ab) - (bc * bc)) / (two * ab);
+    let cy = Real::sqrt(Real::max((ac * ac) - (cx * cx), zero));
+    let sx = ((da * da) - (db * db) + (ab * ab)) / (two * ab);
+    let sy = (da * da) - (sx * sx);
+    if sy < zero {
+        return None;
+    }
+    let sy = -Real::sqrt(sy);
+    if cy - sy <= zero {
+        return None;
+    }
+    // Find where the path from the source to `c` crosses the horizontal axis.
+    let t = -sy / (cy - sy);
+    let x = sx + ((cx - sx) * t);
+    if x < zero || x > ab {
+        return None;
+    }
+    let dx = cx - sx;
+    let dy = cy - sy;
+    Some(Real::sqrt((dx * dx) + (dy * dy)))
+}
+
+#[cfg(test)]
+mod tests {
+    use decorum::R64;
+    use nalgebra::Point3;
+    use std::collections::HashMap;
+
+    use crate::graph::MeshGraph;
+    use crate::index::HashIndexer;
+    use crate::prelude::*;
+    use crate::primitive::generate::Position;
+    use crate::primitive::sphere::UvSphere;
+    use crate::primitive::Trigon;
+
+    type E3 = Point3<R64>;
+
+    #[test]
+    fn planar_geodesic_distances() {
+        // A triangulated strip in the plane. The geodesic distance from the
+        // lower left vertex to the upper right vertex crosses faces and is
+        // shorter than any path along edges.
+        let graph = MeshGraph::<Point3<f64>>::from_raw_buffers(
+            vec![
+                Trigon::new(0usize, 1, 4),
+                Trigon::new(0, 4, 3),
+                Trigon::new(1, 2, 5),
+                Trigon::new(1, 5, 4),
+            ],
+            vec![
+                (0.0, 0.0, 0.0),
+                (1.0, 0.0, 0.0),
+                (2.0, 0.0, 0.0),
+                (0.0, 1.0, 0.0),
+                (1.0, 1.0, 0.0),
+                (2.0, 1.0, 0.0),
+            ],
+        )
+        .unwrap();
+        let key = |x: f64, y: f64| {
+            graph
+                .vertices()
+                .find(|vertex| *vertex.position() == Point3::new(x, y, 0.0))
+                .unwrap()
+                .key()
+        };
+        let distances = graph.geodesic_distances(Some(key(0.0, 0.0))).unwrap();
+
+        assert_eq!(6, distances.len());
+        assert!(distances[&key(0.0, 0.0)].abs() < 1e-6);
+        assert!((distances[&key(2.0, 0.0)] - 2.0).abs() < 1e-6);
+        // The unfolded distance is exact for a planar surface.
+        assert!((distances[&key(2.0, 1.0)] - 5.0f64.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn obtuse_geodesic_distances() {
+        // A planar lattice of triangles with obtuse corners. Rows alternate
+        // between vertices at even and odd horizontal coordinates.
+        let (width, height) = (4usize, 4usize);
+        let mut indices = HashMap::new();
+        let mut positions = Vec::new();
+        for y in 0..=height {
+            for x in ((y % 2)..=(2 * width - (y % 2))).step_by(2) {
+                indices.insert((x, y), positions.len());
+                positions.push((x as f64, (y as f64) * 0.5, 0.0));
+            }
+        }
+        let mut triangles = Vec::new();
+        for y in 0..height {
+            let (lower, upper) = (y % 2, 1 - (y % 2));
+            for x in 0..width {
+                let x = (2 * x) + lower;
+                if x + 2 <= 2 * width - lower {
+                    triangles.push(Trigon::new(
+                        indices[&(x, y)],
+                        indices[&(x + 2, y)],
+                        indices[&(x + 1, y + 1)],
+                    ));
+                }
+                if x >= 1 && x < 2 * width - upper {
+                    triangles.push(Trigon::new(
+                        indices[&(x, y)],
+                        indices[&(x + 1, y + 1)],
+                        indices[&(x - 1, y + 1)],
+                    ));
+                }
+            }
+        }
+        let graph = MeshGraph::<Point3<f64>>::from_raw_buffers(triangles, positions).unwrap();
+        let source = Point3::new(4.0, 0.0, 0.0);
+        let key = graph
+            .vertices()
+            .find(|vertex| *vertex.position() == source)
+            .unwrap()
+            .key();
+        let distances = graph.geodesic_distances(Some(key)).unwrap();
+
+        assert_eq!(graph.vertex_count(), distances.len());
+        // The surface is planar and the straight path from the source to any
+        // vertex away from the jagged sides of the lattice lies on the surface.
+        for vertex in graph.vertices() {
+            let position = vertex.position();
+            if position.x >= 1.0 && position.x <= 7.0 {
+                let expected = (position - source).norm();
+                assert!((distances[&vertex.key()] - expected).abs() < 0.15);
+            }
+        }
+    }
+
+    #[test]
+    fn spherical_geodesic_distances() {
+        let graph: MeshGraph<E3> = UvSphere::new(32, 16)
+            .polygons::<Position<E3>>()
+            .collect_with_indexer(HashIndexer::default())
+            .unwrap();
+        let (north, south) = {
+            let mut vertices = graph.vertices().collect::<Vec<_>>();
+            vertices.sort_by(|a, b| a.position().z.partial_cmp(&b.position().z).unwrap());
+            (vertices.last().unwrap().key(), vertices[0].key())
+        };
+        let distances = graph.geodesic_distances(Some(north)).unwrap();
+
+        // The distance between poles of a unit sphere is approximately π.
+        let distance = distances[&south].into_inner();
+        assert!(distance > 3.0 && distance < 3.3);
+        // Distances increase away from the source.
+        let maximum = distances
+            .values()
+            .map(|distance| distance.into_inner())
+            .fold(0.0, f64::max);
+        assert!((maximum - distance).abs() < 1e-6);
+    }
+}
@@ -7,10 +7,14 @@
 // necessary, constraints are specified there so that they do not pollute user
 // code.
 
+use decorum::Real;
+use num::{NumCast, One, Zero};
+use smallvec::SmallVec;
+use std::fmt::{self, Debug, Formatter};
 use theon::adjunct::FromItems;
-use theon::ops::{Cross, Interpolate, Project};
-use theon::query::Plane;
-use theon::space::{EuclideanSpace, FiniteDimensional, InnerSpace, Vector, VectorSpace};
+use theon::ops::{Cross, Dot, Interpolate, Project};
+use theon::query::{Plane, Unit};
+use theon::space::{EuclideanSpace, FiniteDimensional, InnerSpace, Scalar, Vector, VectorSpace};
 use theon::{AsPosition, Position};
 use typenum::U3;
 
@@ -18,11 +22,15 @@ use crate::entity::borrow::Reborrow;
 use crate::entity::storage::AsStorage;
 use crate::graph::data::{GraphData, Parametric};
 use crate::graph::edge::{Arc, ArcView, Edge, ToArc};
-use crate::graph::face::{Face, ToRing};
+use crate::graph::face::{Face, FaceView, ToRing};
 use crate::graph::mutation::Consistent;
 use crate::graph::vertex::{Vertex, VertexView};
 use crate::graph::{GraphError, OptionExt as _};
 
+/// Tolerance relative to the product of the magnitudes of the rows of a
+/// matrix below which its determinant is considered zero.
+const SINGULAR_TOLERANCE: f64 = 1e-12;
+
 pub type VertexPosition<G> = Position<<G as GraphData>::Vertex>;
 
 pub trait VertexCentroid: GraphData
@@ -56,6 +64,35 @@ where
     }
 }
 
+/// Weighting of face normals in vertex normals.
+///
+/// Vertex normals are computed from the normals of the faces about a vertex.
+/// Weighting determines how much each face contributes to the vertex normal.
+///
+/// See [`VertexNormal`].
+///
+/// [`VertexNormal`]: crate::graph::VertexNormal
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum NormalWeight {
+    /// Each face contributes equally.
+    Uniform,
+    /// Each face contributes in proportion to its area.
+    Area,
+    /// Each face contributes in proportion to the angle of its corner at the
+    /// vertex (Thürmer and Wüthrich).
+    Angle,
+    /// Each face contributes in proportion to the sine of the angle of its
+    /// corner at the vertex divided by the product of the lengths of the edges
+    /// of the corner (Max).
+    Max,
+}
+
+impl Default for NormalWeight {
+    fn default() -> Self {
+        NormalWeight::Uniform
+    }
+}
+
 pub trait VertexNormal: FaceNormal
 where
     Self::Vertex: AsPosition,
@@ -68,6 +105,23 @@ where
             + AsStorage<Vertex<Self>>
             + Consistent
             + Parametric<Data = Self>;
+
+    fn weighted_normal<B>(
+        vertex: VertexView<B>,
+        weight: NormalWeight,
+    ) -> Result<Vector<VertexPosition<Self>>, GraphError>
+    where
+        Self: FaceArea,
+        VertexPosition<Self>: EuclideanSpace,
+        B: Reborrow,
+        B::Target: AsStorage<Arc<Self>>
+            + AsStorage<Face<Self>>
+            + AsStorage<Vertex<Self>>
+            + Consistent
+            + Parametric<Data = Self>,
+    {
+        vertex_normal_with(vertex, weight, <Self as FaceNormal>::normal)
+    }
 }
 
 impl<G> VertexNormal for G
@@ -96,6 +150,256 @@ where
     }
 }
 
+/// Curvature of a surface at a vertex.
+///
+/// See [`VertexCurvature`].
+///
+/// [`VertexCurvature`]: crate::graph::VertexCurvature
+#[derive(Clone, Copy)]
+pub struct Curvature<S>
+where
+    S: EuclideanSpace,
+{
+    /// Gaussian curvature $K$.
+    pub gaussian: Scalar<S>,
+    /// Mean curvature $H$.
+    ///
+    /// The sign of the mean curvature depends on the orientation of the
+    /// surface. It is positive where the surface bends away from its normals,
+    /// such as on a sphere with outward normals, and negative where it bends
+    /// toward them.
+    pub mean: Scalar<S>,
+    /// Maximum principal curvature $\kappa_1=H+\sqrt{H^2-K}$.
+    pub maximum: Scalar<S>,
+    /// Minimum principal curvature $\kappa_2=H-\sqrt{H^2-K}$.
+    pub minimum: Scalar<S>,
+    /// Principal direction of the maximum curvature.
+    pub maximum_direction: Vector<S>,
+    /// Principal direction of the minimum curvature.
+    pub minimum_direction: Vector<S>,
+}
+
+impl<S> Debug for Curvature<S>
+where
+    S: EuclideanSpace,
+    Scalar<S>: Debug,
+    Vector<S>: Debug,
+{
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Curvature")
+            .field("gaussian", &self.gaussian)
+            .field("mean", &self.mean)
+            .field("maximum", &self.maximum)
+            .field("minimum", &self.minimum)
+            .field("maximum_direction", &self.maximum_direction)
+            .field("minimum_direction", &self.minimum_direction)
+            .finish()
+    }
+}
+
+/// Discrete curvature of a surface at a vertex.
+///
+/// Curvature is estimated from the faces about a vertex. These estimates are
+/// exact in the limit for triangles; other faces are approximated by the
+/// triangle formed at each of their corners. Curvature is positive where the
+/// surface bends away from its normals, such as on a sphere with outward
+/// normals.
+pub trait VertexCurvature: VertexNormal
+where
+    Self::Vertex: AsPosition,
+    VertexPosition<Self>: EuclideanSpace,
+{
+    /// Computes the Gaussian curvature by angle defect.
+    ///
+    /// The angle defect is normalized by the barycentric area of the vertex.
+    fn gaussian_curvature<B>(
+        vertex: VertexView<B>,
+    ) -> Result<Scalar<VertexPosition<Self>>, GraphError>
+    where
+        B: Reborrow,
+        B::Target: AsStorage<Arc<Self>>
+            + AsStorage<Face<Self>>
+            + AsStorage<Vertex<Self>>
+            + Consistent
+            + Parametric<Data = Self>;
+
+    /// Computes the mean curvature from the cotangent Laplacian.
+    ///
+    /// The mean curvature is positive where the surface bends away from the
+    /// normal of the vertex.
+    fn mean_curvature<B>(vertex: VertexView<B>) -> Result<Scalar<VertexPosition<Self>>, GraphError>
+    where
+        B: Reborrow,
+        B::Target: AsStorage<Arc<Self>>
+            + AsStorage<Face<Self>>
+            + AsStorage<Vertex<Self>>
+            + Consistent
+            + Parametric<Data = Self>;
+
+    /// Computes the Gaussian, mean, and principal curvatures and the principal
+    /// directions.
+    ///
+    /// Principal directions are estimated by fitting a curvature tensor to the
+    /// normal curvatures along the edges of the vertex.
+    fn curvature<B>(vertex: VertexView<B>) -> Result<Curvature<VertexPosition<Self>>, GraphError>
+    where
+        B: Reborrow,
+        B::Target: AsStorage<Arc<Self>>
+            + AsStorage<Face<Self>>
+            + AsStorage<Vertex<Self>>
+            + Consistent
+            + Parametric<Data = Self>;
+}
+
+impl<G> VertexCurvature for G
+where
+    G: VertexNormal,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace,
+{
+    fn gaussian_curvature<B>(
+        vertex: VertexView<B>,
+    ) -> Result<Scalar<VertexPosition<Self>>, GraphError>
+    where
+        B: Reborrow,
+        B::Target: AsStorage<Arc<Self>>
+            + AsStorage<Face<Self>>
+            + AsStorage<Vertex<Self>>
+            + Consistent
+            + Parametric<Data = Self>,
+    {
+        let one = Scalar::<VertexPosition<Self>>::one();
+        let pi = Real::acos(-one);
+        let area = vertex_area(vertex.to_ref())?;
+        let angle = vertex
+            .outgoing_arcs()
+            .filter(|arc| !arc.is_boundary_arc())
+            .map(|arc| {
+                let p = *vertex.position();
+                let a = *arc.destination_vertex().position() - p;
+                let b = *arc.previous_arc().source_vertex().position() - p;
+                angle(a, b)
+            })
+            .fold(Scalar::<VertexPosition<Self>>::zero(), |sum, angle| {
+                sum + angle
+            });
+        let is_boundary = vertex
+            .outgoing_arcs()
+            .any(|arc| arc.is_boundary_arc() || arc.opposite_arc().is_boundary_arc());
+        let defect = if is_boundary {
+            pi - angle
+        }
+        else {
+            pi + pi - angle
+        };
+        Ok(defect / area)
+    }
+
+    fn mean_curvature<B>(vertex: VertexView<B>) -> Result<Scalar<VertexPosition<Self>>, GraphError>
+    where
+        B: Reborrow,
+        B::Target: AsStorage<Arc<Self>>
+            + AsStorage<Face<Self>>
+            + AsStorage<Vertex<Self>>
+            + Consistent
+            + Parametric<Data = Self>,
+    {
+        let two = Scalar::<VertexPosition<Self>>::one() + One::one();
+        let area = vertex_area(vertex.to_ref())?;
+        let normal = <Self as VertexNormal>::normal(vertex.to_ref())?;
+        let p = *vertex.position();
+        let mut laplacian = Vector::<VertexPosition<Self>>::zero();
+        for arc in vertex.outgoing_arcs() {
+            let q = *arc.destination_vertex().position();
+            let mut weight = Scalar::<VertexPosition<Self>>::zero();
+            // Sum the cotangents of the angles opposite the edge in the faces
+            // on either side of the edge.
+            if !arc.is_boundary_arc() {
+                let r = *arc.next_arc().destination_vertex().position();
+                weight = weight + cotangent(p - r, q - r)?;
+            }
+            let opposite = arc.opposite_arc();
+            if !opposite.is_boundary_arc() {
+                let r = *opposite.next_arc().destination_vertex().position();
+                weight = weight + cotangent(q - r, p - r)?;
+            }
+            laplacian = laplacian + ((q - p) * weight);
+        }
+        let laplacian = laplacian * (Scalar::<VertexPosition<Self>>::one() / (two * area));
+        Ok(-laplacian.dot(normal) / two)
+    }
+
+    fn curvature<B>(vertex: VertexView<B>) -> Result<Curvature<VertexPosition<Self>>, GraphError>
+    where
+        B: Reborrow,
+        B::Target: AsStorage<Arc<Self>>
+            + AsStorage<Face<Self>>
+            + AsStorage<Vertex<Self>>
+            + Consistent
+            + Parametric<Data = Self>,
+    {
+        let zero = Scalar::<VertexPosition<Self>>::zero();
+        let two = Scalar::<VertexPosition<Self>>::one() + One::one();
+        let gaussian = Self::gaussian_curvature(vertex.to_ref())?;
+        let mean = Self::mean_curvature(vertex.to_ref())?;
+        let discriminant = Real::sqrt(clamp_zero((mean * mean) - gaussian));
+        let normal = <Self as VertexNormal>::normal(vertex.to_ref())?;
+        let p = *vertex.position();
+        let tangent = |e: Vector<VertexPosition<Self>>| e - (normal * normal.dot(e));
+        let edges = vertex
+            .adjacent_vertices()
+            .map(|adjacent| *adjacent.position() - p)
+            .collect::<Vec<_>>();
+        // Construct an orthonormal basis of the tangent plane.
+        let u = edges
+            .iter()
+            .filter_map(|e| tangent(*e).normalize())
+            .next()
+            .ok_or(GraphError::Geometry)?;
+        let v = edges
+            .iter()
+            .filter_map(|e| {
+                let e = tangent(*e);
+                (e - (u * u.dot(e))).normalize()
+            })
+            .next()
+            .ok_or(GraphError::Geometry)?;
+        // Fit the tensor $\kappa(\theta)=a\cos^2\theta+2b\cos\theta\sin\theta+c\sin^2\theta$
+        // to the normal curvature along each edge by least squares.
+        let mut m = [[zero; 3]; 3];
+        let mut y = [zero; 3];
+        for e in edges {
+            let t = tangent(e);
+            let (x, z) = (t.dot(u), t.dot(v));
+            let magnitude = Real::sqrt((x * x) + (z * z));
+            if magnitude <= zero {
+                continue;
+            }
+            let (cos, sin) = (x / magnitude, z / magnitude);
+            let kappa = -(two * normal.dot(e)) / e.dot(e);
+            let row = [cos * cos, two * cos * sin, sin * sin];
+            for ((m, y), i) in m.iter_mut().zip(y.iter_mut()).zip(row.iter()) {
+                for (m, j) in m.iter_mut().zip(row.iter()) {
+                    *m = *m + (*i * *j);
+                }
+                *y = *y + (*i * kappa);
+            }
+        }
+        let [a, b, c] = solve(m, y).ok_or(GraphError::Geometry)?;
+        let theta = Real::atan2(two * b, a - c) / two;
+        let (cos, sin) = (Real::cos(theta), Real::sin(theta));
+        Ok(Curvature {
+            gaussian,
+            mean,
+            maximum: mean + discriminant,
+            minimum: mean - discriminant,
+            maximum_direction: (u * cos) + (v * sin),
+            minimum_direction: (v * cos) - (u * sin),
+        })
+    }
+}
+
 pub trait ArcNormal: GraphData
 where
     Self::Vertex: AsPosition,
@@ -202,6 +506,48 @@ where
     }
 }
 
+pub trait FaceArea: GraphData
+where
+    Self::Vertex: AsPosition,
+{
+    fn area<B, T>(ring: T) -> Result<Scalar<VertexPosition<Self>>, GraphError>
+    where
+        B: Reborrow,
+        B::Target:
+            AsStorage<Arc<Self>> + AsStorage<Vertex<Self>> + Consistent + Parametric<Data = Self>,
+        T: ToRing<B>;
+}
+
+impl<G> FaceArea for G
+where
+    G: FaceCentroid + GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace,
+{
+    fn area<B, T>(ring: T) -> Result<Scalar<VertexPosition<Self>>, GraphError>
+    where
+        B: Reborrow,
+        B::Target:
+            AsStorage<Arc<Self>> + AsStorage<Vertex<Self>> + Consistent + Parametric<Data = Self>,
+        T: ToRing<B>,
+    {
+        // Sum the areas of a fan of triangles about the centroid, which
+        // supports non-planar faces.
+        let ring = ring.into_ring();
+        let c = G::centroid(ring.to_ref())?;
+        Ok(ring
+            .arcs()
+            .map(|arc| {
+                let a = *arc.source_vertex().position() - c;
+                let b = *arc.destination_vertex().position() - c;
+                area(a, b)
+            })
+            .fold(Scalar::<VertexPosition<Self>>::zero(), |sum, area| {
+                sum + area
+            }))
+    }
+}
+
 pub trait FaceNormal: GraphData
 where
     Self::Vertex: AsPosition,
@@ -228,14 +574,22 @@ where
             AsStorage<Arc<Self>> + AsStorage<Vertex<Self>> + Consistent + Parametric<Data = Self>,
         T: ToRing<B>,
     {
+        // Use Newell's method, which sums the cross products of each edge
+        // about the centroid. This is robust for concave and non-planar faces
+        // and only fails for degenerate faces with no area.
         let ring = ring.into_ring();
-        let (a, b) =
-            FromItems::from_items(ring.vertices().take(2).map(|vertex| *vertex.position()))
-                .expect_consistent();
-        let c = G::centroid(ring)?;
-        let ab = a - b;
-        let bc = b - c;
-        ab.cross(bc).normalize().ok_or(GraphError::Geometry)
+        let c = G::centroid(ring.to_ref())?;
+        ring.arcs()
+            .map(|arc| {
+                let a = *arc.source_vertex().position() - c;
+                let b = *arc.destination_vertex().position() - c;
+                a.cross(b)
+            })
+            .fold(Vector::<VertexPosition<Self>>::zero(), |sum, normal| {
+                sum + normal
+            })
+            .normalize()
+            .ok_or(GraphError::Geometry)
     }
 }
 
@@ -252,47 +606,279 @@ where
         T: ToRing<B>;
 }
 
-// TODO: The `lapack` feature depends on `ndarray-linalg` and Intel MKL. MKL is
-//       dynamically linked, but the linkage fails during doctests and may fail
-//       when launching a binary. The `lapack` feature and this implementation
-//       have been disabled until an upstream fix is available. See
-//       https://github.com/olson-sean-k/plexus/issues/58 and
-//       https://github.com/rust-ndarray/ndarray-linalg/issues/229
-// TODO: The `lapack` feature only supports Linux. See
-//       https://github.com/olson-sean-k/theon/issues/1
-//
-//#[cfg(target_os = "linux")]
-//mod lapack {
-//    use super::*;
-//
-//    use smallvec::SmallVec;
-//    use theon::adjunct::{FromItems, IntoItems};
-//    use theon::lapack::Lapack;
-//    use theon::space::Scalar;
-//
-//    impl<G> FacePlane for G
-//    where
-//        G: GraphData,
-//        G::Vertex: AsPosition,
-//        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
-//        Scalar<VertexPosition<G>>: Lapack,
-//        Vector<VertexPosition<G>>: FromItems + IntoItems,
-//    {
-//        fn plane<B, T>(ring: T) -> Result<Plane<VertexPosition<G>>, GraphError>
-//        where
-//            B: Reborrow,
-//            B::Target: AsStorage<Arc<Self>>
-//                + AsStorage<Vertex<Self>>
-//                + Consistent
-//                + Parametric<Data = Self>,
-//            T: ToRing<B>,
-//        {
-//            let ring = ring.into_ring();
-//            let points = ring
-//                .vertices()
-//                .map(|vertex| *vertex.data.as_position())
-//                .collect::<SmallVec<[_; 4]>>();
-//            Plane::from_points(points).ok_or(GraphError::Geometry)
-//        }
-//    }
-//}
+impl<G> FacePlane for G
+where
+    G: FaceCentroid + FaceNormal,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+{
+    fn plane<B, T>(ring: T) -> Result<Plane<VertexPosition<Self>>, GraphError>
+    where
+        B: Reborrow,
+        B::Target:
+            AsStorage<Arc<Self>> + AsStorage<Vertex<Self>> + Consistent + Parametric<Data = Self>,
+        T: ToRing<B>,
+    {
+        // The centroid minimizes the squared distances of the vertices to any
+        // plane with a given normal, and the Newell normal of the face (see
+        // `FaceNormal`) is a robust estimate of the normal of the best-fit
+        // plane. This requires no linear algebra beyond cross products.
+        let ring = ring.into_ring();
+        let origin = <Self as FaceCentroid>::centroid(ring.to_ref())?;
+        let normal = <Self as FaceNormal>::normal(ring)?;
+        Ok(Plane {
+            origin,
+            normal: Unit::try_from_inner(normal).ok_or(GraphError::Geometry)?,
+        })
+    }
+}
+
+/// Computes the barycentric area of a vertex.
+///
+/// This is one third of the area of the triangles formed at the corners of the
+/// faces about the vertex.
+fn vertex_area<B, G>(vertex: VertexView<B>) -> Result<Scalar<VertexPosition<G>>, GraphError>
+where
+    B: Reborrow,
+    B::Target: AsStorage<Arc<G>>
+        + AsStorage<Face<G>>
+        + AsStorage<Vertex<G>>
+        + Consistent
+        + Parametric<Data = G>,
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace,
+{
+    let three = Scalar::<VertexPosition<G>>::one() + One::one() + One::one();
+    let p = *vertex.position();
+    let area = vertex
+        .outgoing_arcs()
+        .filter(|arc| !arc.is_boundary_arc())
+        .map(|arc| {
+            let a = *arc.destination_vertex().position() - p;
+            let b = *arc.previous_arc().source_vertex().position() - p;
+            area(a, b)
+        })
+        .fold(Scalar::<VertexPosition<G>>::zero(), |sum, area| sum + area)
+        / three;
+    if area > Zero::zero() {
+        Ok(area)
+    }
+    else {
+        Err(GraphError::Geometry)
+    }
+}
+
+/// Computes the angle between two vectors.
+pub(in crate::graph) fn angle<V>(a: V, b: V) -> V::Scalar
+where
+    V: InnerSpace,
+{
+    let one = V::Scalar::one();
+    let cos = a.dot(b) / (a.magnitude() * b.magnitude());
+    Real::acos(if cos > one {
+        one
+    }
+    else if cos < -one {
+        -one
+    }
+    else {
+        cos
+    })
+}
+
+/// Computes the area of the triangle formed by two vectors.
+pub(in crate::graph) fn area<V>(a: V, b: V) -> V::Scalar
+where
+    V: InnerSpace,
+{
+    let two = V::Scalar::one() + One::one();
+    let ab = a.dot(b);
+    Real::sqrt(clamp_zero((a.dot(a) * b.dot(b)) - (ab * ab))) / two
+}
+
+/// Computes the cotangent of the angle between two vectors.
+pub(in crate::graph) fn cotangent<V>(a: V, b: V) -> Result<V::Scalar, GraphError>
+where
+    V: InnerSpace,
+{
+    let ab = a.dot(b);
+    let sin = Real::sqrt(clamp_zero((a.dot(a) * b.dot(b)) - (ab * ab)));
+    if sin > Zero::zero() {
+        Ok(ab / sin)
+    }
+    else {
+        Err(GraphError::Geometry)
+    }
+}
+
+/// Triangulates a ring of positions by ear clipping.
+///
+/// Returns triangles as indices into the ring in winding order. Ears are
+/// determined with respect to the plane of the ring (given by its Newell
+/// normal), so the triangles cover exactly the ring when it is planar and
+/// simple, including when it is concave. If no ear can be found, such as when
+/// the ring is degenerate or self-intersecting, then the remaining ring is
+/// triangulated as a fan.
+pub(in crate::graph) fn triangulate<S>(ring: &[S]) -> SmallVec<[[usize; 3]; 8]>
+where
+    S: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<S>: Cross<Output = Vector<S>>,
+{
+    let zero = Scalar::<S>::zero();
+    let mut triangles = SmallVec::new();
+    if ring.len() < 3 {
+        return triangles;
+    }
+    let normal = (1..ring.len()).fold(Vector::<S>::zero(), |normal, index| {
+        let next = (index + 1) % ring.len();
+        normal + (ring[index] - ring[0]).cross(ring[next] - ring[0])
+    });
+    // Gets the (scaled) sine of the turn from `ab` to `bc` with respect to the
+    // plane of the ring.
+    let turn = |a: S, b: S, c: S| normal.dot((b - a).cross(c - b));
+    let mut indices = (0..ring.len()).collect::<SmallVec<[_; 8]>>();
+    while indices.len() > 3 {
+        let n = indices.len();
+        let ear = (0..n)
+            .find(|&index| {
+                let (a, b, c) = (
+                    indices[(index + n - 1) % n],
+                    indices[index],
+                    indices[(index + 1) % n],
+                );
+                turn(ring[a], ring[b], ring[c]) > zero
+                    && indices
+                        .iter()
+                        .filter(|&&other| other != a && other != b && other != c)
+                        .all(|&other| {
+                            let p = ring[other];
+                            turn(ring[a], ring[b], p) < zero
+                                || turn(ring[b], ring[c], p) < zero
+                                || turn(ring[c], ring[a], p) < zero
+                        })
+            })
+            .unwrap_or(1);
+        triangles.push([
+            indices[(ear + n - 1) % n],
+            indices[ear],
+            indices[(ear + 1) % n],
+        ]);
+        indices.remove(ear);
+    }
+    triangles.push([indices[0], indices[1], indices[2]]);
+    triangles
+}
+
+/// Clamps negative values (typically from rounding errors) to zero.
+fn clamp_zero<T>(x: T) -> T
+where
+    T: PartialOrd + Zero,
+{
+    if x < T::zero() {
+        T::zero()
+    }
+    else {
+        x
+    }
+}
+
+/// Solves a system of three linear equations using Cramer's rule.
+fn solve<T>(m: [[T; 3]; 3], y: [T; 3]) -> Option<[T; 3]>
+where
+    T: NumCast + Real,
+{
+    let determinant = |m: [[T; 3]; 3]| {
+        (m[0][0] * ((m[1][1] * m[2][2]) - (m[1][2] * m[2][1])))
+            - (m[0][1] * ((m[1][0] * m[2][2]) - (m[1][2] * m[2][0])))
+            + (m[0][2] * ((m[1][0] * m[2][1]) - (m[1][1] * m[2][0])))
+    };
+    let d = determinant(m);
+    // The magnitude of the determinant is bounded by the product of the
+    // magnitudes of the rows. Reject systems that are nearly singular with
+    // respect to this bound rather than only those that are exactly singular.
+    let tolerance = <T as NumCast>::from(SINGULAR_TOLERANCE).unwrap();
+    let bound = m.iter().fold(T::one(), |bound, row| {
+        bound * row.iter().fold(T::zero(), |sum, x| sum + (*x * *x))
+    });
+    if d * d <= bound * tolerance * tolerance {
+        return None;
+    }
+    let mut x = [T::zero(); 3];
+    for (i, x) in x.iter_mut().enumerate() {
+        let mut n = m;
+        for (row, y) in n.iter_mut().zip(y.iter()) {
+            row[i] = *y;
+        }
+        *x = determinant(n) / d;
+    }
+    Some(x)
+}
+
+/// Computes a weighted vertex normal from face normals given by a function.
+///
+/// This allows face normals to be cached when computing many vertex normals.
+pub(in crate::graph) fn vertex_normal_with<B, G, F>(
+    vertex: VertexView<B>,
+    weight: NormalWeight,
+    mut f: F,
+) -> Result<Vector<VertexPosition<G>>, GraphError>
+where
+    B: Reborrow,
+    B::Target: AsStorage<Arc<G>>
+        + AsStorage<Face<G>>
+        + AsStorage<Vertex<G>>
+        + Consistent
+        + Parametric<Data = G>,
+    G: FaceArea,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace,
+    F: FnMut(FaceView<&B::Target>) -> Result<Vector<VertexPosition<G>>, GraphError>,
+{
+    let mut normal = Vector::<VertexPosition<G>>::zero();
+    for arc in vertex.outgoing_arcs() {
+        if let Some(face) = arc.face() {
+            normal = normal + (f(face)? * corner_weight(arc, weight)?);
+        }
+    }
+    normal.normalize().ok_or(GraphError::Geometry)
+}
+
+/// Computes the weight of the normal of a face at its corner at the source
+/// vertex of the given arc.
+///
+/// The arc must be part of the face.
+pub(in crate::graph) fn corner_weight<B, G>(
+    arc: ArcView<B>,
+    weight: NormalWeight,
+) -> Result<Scalar<VertexPosition<G>>, GraphError>
+where
+    B: Reborrow,
+    B::Target: AsStorage<Arc<G>>
+        + AsStorage<Face<G>>
+        + AsStorage<Vertex<G>>
+        + Consistent
+        + Parametric<Data = G>,
+    G: FaceArea,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace,
+{
+    let p = *arc.source_vertex().position();
+    let a = *arc.destination_vertex().position() - p;
+    let b = *arc.previous_arc().source_vertex().position() - p;
+    Ok(match weight {
+        NormalWeight::Uniform => One::one(),
+        NormalWeight::Area => G::area(arc.face().ok_or(GraphError::TopologyNotFound)?)?,
+        NormalWeight::Angle => angle(a, b),
+        NormalWeight::Max => {
+            let two = Scalar::<VertexPosition<G>>::one() + One::one();
+            let product = a.dot(a) * b.dot(b);
+            if product > Zero::zero() {
+                (two * area(a, b)) / product
+            }
+            else {
+                return Err(GraphError::Geometry);
+            }
+        }
+    })
+}
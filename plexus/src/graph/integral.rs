@@ -0,0 +1,110 @@
+//! Integral geometry.
+//!
+//! Mass properties are computed by decomposing the solid bounded by a graph
+//! into signed tetrahedra, each formed by the origin and a triangle of a fan
+//! about the centroid of a face. Densities are uniform and unit.
+
+use decorum::Real;
+use num::{One, Zero};
+use theon::space::{EuclideanSpace, FiniteDimensional, Scalar};
+use theon::AsPosition;
+use typenum::U3;
+
+use crate::graph::data::GraphData;
+use crate::graph::face::FaceView;
+use crate::graph::geometry::{FaceCentroid, VertexPosition};
+use crate::graph::MeshGraph;
+
+type Triple<T> = [T; 3];
+
+/// Volume and first and second moments of a solid about the origin.
+pub(in crate::graph) struct Moments<T> {
+    pub volume: T,
+    pub first: Triple<T>,
+    pub second: Triple<Triple<T>>,
+}
+
+impl<T> Moments<T>
+where
+    T: Real,
+{
+    /// Gets the center of mass.
+    pub fn center(&self) -> Option<Triple<T>> {
+        if self.volume == T::zero() {
+            None
+        }
+        else {
+            Some([
+                self.first[0] / self.volume,
+                self.first[1] / self.volume,
+                self.first[2] / self.volume,
+            ])
+        }
+    }
+
+    /// Gets the inertia tensor about the center of mass.
+    pub fn inertia(&self) -> Option<Triple<Triple<T>>> {
+        let center = self.center()?;
+        // Translate the second moment (covariance) to the center of mass.
+        let mut covariance = self.second;
+        for (i, row) in covariance.iter_mut().enumerate() {
+            for (j, entry) in row.iter_mut().enumerate() {
+                *entry = *entry - (self.volume * center[i] * center[j]);
+            }
+        }
+        let trace = covariance[0][0] + covariance[1][1] + covariance[2][2];
+        let mut inertia = [[T::zero(); 3]; 3];
+        for (i, row) in inertia.iter_mut().enumerate() {
+            for (j, entry) in row.iter_mut().enumerate() {
+                *entry = (if i == j { trace } else { T::zero() }) - covariance[i][j];
+            }
+        }
+        Some(inertia)
+    }
+}
+
+/// Computes the moments of the solid bounded by the given faces.
+pub(in crate::graph) fn moments<'a, G, I>(faces: I) -> Moments<Scalar<VertexPosition<G>>>
+where
+    G: 'a + FaceCentroid + GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    I: IntoIterator<Item = FaceView<&'a MeshGraph<G>>>,
+{
+    let zero = Scalar::<VertexPosition<G>>::zero();
+    let one = Scalar::<VertexPosition<G>>::one();
+    let two = one + one;
+    let six = two * (two + one);
+    let twenty_four = six * (two + two);
+    let one_hundred_twenty = twenty_four * (two + two + one);
+    let mut moments = Moments {
+        volume: zero,
+        first: [zero; 3],
+        second: [[zero; 3]; 3],
+    };
+    for face in faces {
+        let (cx, cy, cz) = face.centroid().into_xyz();
+        let p = [cx, cy, cz];
+        for arc in face.adjacent_arcs() {
+            let (ax, ay, az) = (*arc.source_vertex().position()).into_xyz();
+            let (bx, by, bz) = (*arc.destination_vertex().position()).into_xyz();
+            let (q, r) = ([ax, ay, az], [bx, by, bz]);
+            // Six times the signed volume of the tetrahedron.
+            let determinant = (p[0] * ((q[1] * r[2]) - (q[2] * r[1])))
+                - (p[1] * ((q[0] * r[2]) - (q[2] * r[0])))
+                + (p[2] * ((q[0] * r[1]) - (q[1] * r[0])));
+            let s = [p[0] + q[0] + r[0], p[1] + q[1] + r[1], p[2] + q[2] + r[2]];
+            moments.volume = moments.volume + (determinant / six);
+            for (i, first) in moments.first.iter_mut().enumerate() {
+                *first = *first + ((determinant * s[i]) / twenty_four);
+            }
+            for (i, row) in moments.second.iter_mut().enumerate() {
+                for (j, second) in row.iter_mut().enumerate() {
+                    let outer = (p[i] * p[j]) + (q[i] * q[j]) + (r[i] * r[j]) + (s[i] * s[j]);
+                    *second = *second + ((determinant * outer) / one_hundred_twenty);
+                }
+            }
+        }
+    }
+    moments
+}
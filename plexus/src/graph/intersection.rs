@@ -0,0 +1,596 @@
+//! Intersections between faces.
+//!
+//! Candidate pairs of faces are found with a bounding volume hierarchy and
+//! faces are intersected as triangulations of their rings. Each pair of
+//! triangles is intersected by clipping each triangle against the plane of the
+//! other, which yields two segments along the line in which the planes meet.
+//! The intersection is the overlap of these segments. Coplanar triangles are
+//! instead intersected in their shared plane: the edges of each face that
+//! belong to a triangle are clipped against the other triangle, which yields
+//! the boundary of their overlap.
+//!
+//! Adjacent faces always meet at the vertices and edges that they share.
+//! Triangles of adjacent faces that share two vertices and are not coplanar
+//! only meet along the segment between them and are not intersected. Segments
+//! at which triangles only touch at a shared vertex or that lie along a shared
+//! edge are discarded.
+
+use num::{NumCast, One, Zero};
+use smallvec::SmallVec;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use theon::ops::{Cross, Dot};
+use theon::space::{EuclideanSpace, FiniteDimensional, Scalar, Vector};
+use theon::AsPosition;
+use typenum::U3;
+
+use crate::graph::data::GraphData;
+use crate::graph::face::{FaceKey, FaceView};
+use crate::graph::geometry::{self, VertexPosition};
+use crate::graph::vertex::VertexKey;
+use crate::graph::{MeshGraph, OptionExt as _};
+
+/// Tolerance relative to the size of a triangle below which a segment is
+/// considered degenerate.
+const TOLERANCE: f64 = 1e-7;
+
+/// Triangles along with the keys of their vertices and whether or not each of
+/// their edges (from the vertex at the same index to the next) is an edge of
+/// the face.
+type Triangles<S> = SmallVec<[([S; 3], [VertexKey; 3], [bool; 3]); 8]>;
+
+/// Intersection of a pair of faces.
+#[derive(Clone)]
+pub struct FaceIntersection<S>
+where
+    S: EuclideanSpace,
+{
+    /// The keys of the intersecting faces.
+    pub faces: (FaceKey, FaceKey),
+    /// The segments along which the faces intersect.
+    ///
+    /// A segment may be degenerate (its endpoints may be the same) if the
+    /// faces only touch at a point. If the faces are coplanar, then the
+    /// segments are the portions of the edges of each face that lie within
+    /// the other and bound their overlap.
+    pub segments: Vec<(S, S)>,
+}
+
+/// Finds the intersections between the faces of a graph.
+///
+/// Adjacent faces are intersected, but the vertices and edges that they share
+/// are not considered intersections.
+pub(in crate::graph) fn self_intersections<G>(
+    graph: &MeshGraph<G>,
+) -> Vec<FaceIntersection<VertexPosition<G>>>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+{
+    let bvh = graph.bvh();
+    let mut pairs = HashSet::new();
+    let mut intersections = Vec::new();
+    for (a, b) in bvh.intersections(&bvh) {
+        if a == b || pairs.contains(&(*b, *a)) {
+            continue;
+        }
+        pairs.insert((*a, *b));
+        let (a, b) = (
+            graph.face(*a).expect_consistent(),
+            graph.face(*b).expect_consistent(),
+        );
+        let is_adjacent = a.adjacent_vertices().any(|vertex| {
+            b.adjacent_vertices()
+                .any(|other| vertex.key() == other.key())
+        });
+        intersections.extend(intersect(a, b, is_adjacent));
+    }
+    intersections
+}
+
+/// Finds the intersections between the faces of two graphs.
+pub(in crate::graph) fn intersections<G, H>(
+    graph: &MeshGraph<G>,
+    other: &MeshGraph<H>,
+) -> Vec<FaceIntersection<VertexPosition<G>>>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    H: GraphData,
+    H::Vertex: AsPosition<Position = VertexPosition<G>>,
+    VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+{
+    let (left, right) = (graph.bvh(), other.bvh());
+    left.intersections(&right)
+        .into_iter()
+        .filter_map(|(a, b)| {
+            intersect(
+                graph.face(*a).expect_consistent(),
+                other.face(*b).expect_consistent(),
+                false,
+            )
+        })
+        .collect()
+}
+
+/// Intersects two faces.
+///
+/// If the faces are adjacent, then their shared vertices and edges are
+/// excluded from the intersection.
+fn intersect<G, H>(
+    a: FaceView<&MeshGraph<G>>,
+    b: FaceView<&MeshGraph<H>>,
+    is_adjacent: bool,
+) -> Option<FaceIntersection<VertexPosition<G>>>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    H: GraphData,
+    H::Vertex: AsPosition<Position = VertexPosition<G>>,
+    VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+{
+    let (triangles, others) = (triangles(a), triangles(b));
+    let mut segments = Vec::new();
+    for (triangle, keys, edges) in triangles.iter() {
+        for (other, other_keys, other_edges) in others.iter() {
+            let shared = if is_adjacent {
+                (0..3)
+                    .filter(|index| other_keys.contains(&keys[*index]))
+                    .collect::<SmallVec<[_; 3]>>()
+            }
+            else {
+                SmallVec::new()
+            };
+            if is_coplanar(triangle, other) {
+                segments.extend(
+                    coplanar_triangle_triangle((triangle, *edges), (other, *other_edges))
+                        .into_iter()
+                        .filter(|segment| match shared.len() {
+                            0 => true,
+                            1 => !is_degenerate(segment, triangle),
+                            _ => {
+                                !is_degenerate(segment, triangle)
+                                    && !is_collinear(
+                                        segment,
+                                        (triangle[shared[0]], triangle[shared[1]]),
+                                        triangle,
+                                    )
+                            }
+                        }),
+                );
+            }
+            else {
+                match shared.len() {
+                    0 => segments.extend(triangle_triangle(triangle, other)),
+                    1 => segments.extend(
+                        triangle_triangle(triangle, other)
+                            .filter(|segment| !is_degenerate(segment, triangle)),
+                    ),
+                    // The triangles only meet along the segment between the
+                    // vertices that they share.
+                    _ => {}
+                }
+            }
+        }
+    }
+    if segments.is_empty() {
+        None
+    }
+    else {
+        Some(FaceIntersection {
+            faces: (a.key(), b.key()),
+            segments,
+        })
+    }
+}
+
+/// Gets the triangles of the triangulation of a face.
+fn triangles<G>(face: FaceView<&MeshGraph<G>>) -> Triangles<VertexPosition<G>>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+{
+    let (keys, positions): (SmallVec<[_; 8]>, SmallVec<[_; 8]>) = face
+        .adjacent_vertices()
+        .map(|vertex| (vertex.key(), *vertex.position()))
+        .unzip();
+    geometry::triangulate(&positions)
+        .into_iter()
+        .map(|[a, b, c]| {
+            let is_edge = |from: usize, to: usize| to == (from + 1) % positions.len();
+            (
+                [positions[a], positions[b], positions[c]],
+                [keys[a], keys[b], keys[c]],
+                [is_edge(a, b), is_edge(b, c), is_edge(c, a)],
+            )
+        })
+        .collect()
+}
+
+/// Intersects two triangles.
+///
+/// Returns the segment along which the triangles intersect or `None` if the
+/// triangles do not intersect or are parallel.
+fn triangle_triangle<S>(a: &[S; 3], b: &[S; 3]) -> Option<(S, S)>
+where
+    S: EuclideanSpace,
+    Vector<S>: Cross<Output = Vector<S>>,
+{
+    let na = normal(a);
+    let nb = normal(b);
+    let direction = na.cross(nb);
+    if direction.dot(direction) == Zero::zero() {
+        // The triangles are parallel or degenerate.
+        return None;
+    }
+    // Both segments lie along the line in which the planes meet. Project
+    // their endpoints onto that line and find the overlap.
+    let origin = a[0];
+    let project = |points: SmallVec<[S; 3]>| {
+        let mut points = points
+            .into_iter()
+            .map(|point| (direction.dot(point - origin), point))
+            .collect::<SmallVec<[_; 3]>>();
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        (points[0], points[points.len() - 1])
+    };
+    let ((ta0, a0), (ta1, a1)) = project(clip(a, nb, b[0])?);
+    let ((tb0, _), (tb1, _)) = project(clip(b, na, a[0])?);
+    let lower = if ta0 > tb0 { ta0 } else { tb0 };
+    let upper = if ta1 < tb1 { ta1 } else { tb1 };
+    if lower > upper {
+        return None;
+    }
+    let along = |t: Scalar<S>| {
+        if ta1 == ta0 {
+            a0
+        }
+        else {
+            a0 + ((a1 - a0) * ((t - ta0) / (ta1 - ta0)))
+        }
+    };
+    Some((along(lower), along(upper)))
+}
+
+/// Intersects two coplanar triangles.
+///
+/// Returns the portions of the face edges of each triangle that lie within
+/// the other triangle. Together, these segments bound the overlap of the
+/// faces.
+fn coplanar_triangle_triangle<S>(
+    a: (&[S; 3], [bool; 3]),
+    b: (&[S; 3], [bool; 3]),
+) -> SmallVec<[(S, S); 6]>
+where
+    S: EuclideanSpace,
+    Vector<S>: Cross<Output = Vector<S>>,
+{
+    let mut segments = SmallVec::new();
+    for ((triangle, edges), other) in &[(a, b.0), (b, a.0)] {
+        for (index, is_edge) in edges.iter().enumerate() {
+            if *is_edge {
+                let segment = (triangle[index], triangle[(index + 1) % 3]);
+                segments.extend(clip_segment(segment, other));
+            }
+        }
+    }
+    segments
+}
+
+/// Clips a segment against a coplanar triangle.
+///
+/// Returns the portion of the segment that lies within the triangle or `None`
+/// if the segment is entirely outside of the triangle.
+fn clip_segment<S>(segment: (S, S), triangle: &[S; 3]) -> Option<(S, S)>
+where
+    S: EuclideanSpace,
+    Vector<S>: Cross<Output = Vector<S>>,
+{
+    let zero = Scalar::<S>::zero();
+    let n = normal(triangle);
+    if n.dot(n) == zero {
+        return None;
+    }
+    let (p, q) = segment;
+    let (mut lower, mut upper) = (zero, Scalar::<S>::one());
+    for index in 0..3 {
+        // Points within the triangle are on the inner side of each of its
+        // edges.
+        let (a, b) = (triangle[index], triangle[(index + 1) % 3]);
+        let side = |point: S| n.dot((b - a).cross(point - a));
+        let (sp, sq) = (side(p), side(q));
+        if sp < zero && sq < zero {
+            return None;
+        }
+        else if sp < zero {
+            let t = sp / (sp - sq);
+            if t > lower {
+                lower = t;
+            }
+        }
+        else if sq < zero {
+            let t = sp / (sp - sq);
+            if t < upper {
+                upper = t;
+            }
+        }
+    }
+    if lower > upper {
+        None
+    }
+    else {
+        Some((p + ((q - p) * lower), p + ((q - p) * upper)))
+    }
+}
+
+/// Clips a triangle against a plane.
+///
+/// Returns the points at which the edges of the triangle meet the plane or
+/// `None` if the triangle is entirely on one side of the plane.
+fn clip<S>(triangle: &[S; 3], normal: Vector<S>, origin: S) -> Option<SmallVec<[S; 3]>>
+where
+    S: EuclideanSpace,
+{
+    let zero = Scalar::<S>::zero();
+    let distances = [
+        normal.dot(triangle[0] - origin),
+        normal.dot(triangle[1] - origin),
+        normal.dot(triangle[2] - origin),
+    ];
+    if distances.iter().all(|distance| *distance > zero)
+        || distances.iter().all(|distance| *distance < zero)
+    {
+        return None;
+    }
+    let mut points = SmallVec::new();
+    for (index, (position, distance)) in triangle.iter().zip(&distances).enumerate() {
+        let next = (index + 1) % 3;
+        let (other, other_distance) = (triangle[next], distances[next]);
+        if *distance == zero {
+            points.push(*position);
+        }
+        else if (*distance < zero && other_distance > zero)
+            || (*distance > zero && other_distance < zero)
+        {
+            let t = *distance / (*distance - other_distance);
+            points.push(*position + ((other - *position) * t));
+        }
+    }
+    if points.is_empty() {
+        None
+    }
+    else {
+        Some(points)
+    }
+}
+
+/// Determines if two triangles are coplanar.
+///
+/// Degenerate triangles are never coplanar.
+fn is_coplanar<S>(a: &[S; 3], b: &[S; 3]) -> bool
+where
+    S: EuclideanSpace,
+    Vector<S>: Cross<Output = Vector<S>>,
+{
+    let zero = Scalar::<S>::zero();
+    let tolerance = <Scalar<S> as NumCast>::from(TOLERANCE).unwrap();
+    let (na, nb) = (normal(a), normal(b));
+    let direction = na.cross(nb);
+    if na.dot(na) == zero || nb.dot(nb) == zero || direction.dot(direction) != zero {
+        return false;
+    }
+    let bound = na.dot(na) * size(a) * tolerance * tolerance;
+    b.iter().all(|point| {
+        let distance = na.dot(*point - a[0]);
+        distance * distance <= bound
+    })
+}
+
+/// Determines if a segment is degenerate with respect to the size of a
+/// triangle.
+fn is_degenerate<S>(segment: &(S, S), triangle: &[S; 3]) -> bool
+where
+    S: EuclideanSpace,
+{
+    let tolerance = <Scalar<S> as NumCast>::from(TOLERANCE).unwrap();
+    let length = segment.1 - segment.0;
+    length.dot(length) <= size(triangle) * tolerance * tolerance
+}
+
+/// Determines if a segment lies along the line through two points with
+/// respect to the size of a triangle.
+fn is_collinear<S>(segment: &(S, S), line: (S, S), triangle: &[S; 3]) -> bool
+where
+    S: EuclideanSpace,
+    Vector<S>: Cross<Output = Vector<S>>,
+{
+    let tolerance = <Scalar<S> as NumCast>::from(TOLERANCE).unwrap();
+    let direction = line.1 - line.0;
+    let bound = direction.dot(direction) * size(triangle) * tolerance * tolerance;
+    [segment.0, segment.1].iter().all(|point| {
+        let offset = direction.cross(*point - line.0);
+        offset.dot(offset) <= bound
+    })
+}
+
+/// Gets the squared length of the longest edge of a triangle.
+fn size<S>(triangle: &[S; 3]) -> Scalar<S>
+where
+    S: EuclideanSpace,
+{
+    (0..3).fold(Scalar::<S>::zero(), |size, index| {
+        let edge = triangle[(index + 1) % 3] - triangle[index];
+        let length = edge.dot(edge);
+        if length > size {
+            length
+        }
+        else {
+            size
+        }
+    })
+}
+
+fn normal<S>(triangle: &[S; 3]) -> Vector<S>
+where
+    S: EuclideanSpace,
+    Vector<S>: Cross<Output = Vector<S>>,
+{
+    (triangle[1] - triangle[0]).cross(triangle[2] - triangle[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{Point3, Vector3};
+
+    use crate::graph::{fixture, MeshGraph};
+    use crate::prelude::*;
+    use crate::primitive::cube::Cube;
+    use crate::primitive::generate::Position;
+    use crate::primitive::Tetragon;
+
+    type E3 = Point3<f64>;
+
+    #[test]
+    fn self_intersection() {
+        let graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+        assert!(!graph.is_self_intersecting());
+        assert!(!graph.intersections(&graph).is_empty());
+
+        // Two cubes that pass through one another.
+        let offset = Vector3::new(0.5, 0.25, 0.125);
+        let other: MeshGraph<E3> = Cube::new()
+            .polygons::<Position<E3>>()
+            .map_vertices(|position| position + offset)
+            .collect();
+        let intersections = graph.intersections(&other);
+        assert!(!intersections.is_empty());
+        for intersection in intersections.iter() {
+            for (a, b) in intersection.segments.iter() {
+                // Segments lie within both cubes.
+                for point in &[a, b] {
+                    assert!(point.x >= 0.0 - 1e-9 && point.x <= 0.5 + 1e-9);
+                    assert!(point.y >= -0.25 - 1e-9 && point.y <= 0.5 + 1e-9);
+                    assert!(point.z >= -0.375 - 1e-9 && point.z <= 0.5 + 1e-9);
+                }
+            }
+        }
+        let graph: MeshGraph<E3> = Cube::new()
+            .polygons::<Position<E3>>()
+            .chain(
+                Cube::new()
+                    .polygons::<Position<E3>>()
+                    .map_vertices(|position| position + offset),
+            )
+            .collect();
+        assert!(graph.is_self_intersecting());
+        assert_eq!(intersections.len(), graph.self_intersections().len());
+    }
+
+    #[test]
+    fn adjacent_self_intersection() {
+        // Two quadrilaterals that share an edge. The second is folded through
+        // the first.
+        let graph = MeshGraph::<E3>::from_raw_buffers(
+            vec![Tetragon::new(0usize, 1, 2, 3), Tetragon::new(0, 3, 4, 5)],
+            vec![
+                (0.0, 0.0, 0.0),
+                (1.0, 0.0, 0.0),
+                (1.0, 1.0, 0.0),
+                (0.0, 1.0, 0.0),
+                (0.6, 0.9, -0.5),
+                (0.6, 0.1, 0.5),
+            ],
+        )
+        .unwrap();
+        assert!(graph.is_self_intersecting());
+        assert_eq!(1, graph.self_intersections().len());
+
+        // The same quadrilaterals folded along their shared edge without
+        // passing through one another.
+        let graph = MeshGraph::<E3>::from_raw_buffers(
+            vec![Tetragon::new(0usize, 1, 2, 3), Tetragon::new(0, 3, 4, 5)],
+            vec![
+                (0.0, 0.0, 0.0),
+                (1.0, 0.0, 0.0),
+                (1.0, 1.0, 0.0),
+                (0.0, 1.0, 0.0),
+                (0.0, 1.0, 1.0),
+                (0.0, 0.0, 1.0),
+            ],
+        )
+        .unwrap();
+        assert!(!graph.is_self_intersecting());
+    }
+
+    #[test]
+    fn coplanar_self_intersection() {
+        // Two overlapping quadrilaterals in the same plane.
+        let graph = MeshGraph::<E3>::from_raw_buffers(
+            vec![Tetragon::new(0usize, 1, 2, 3), Tetragon::new(4, 5, 6, 7)],
+            vec![
+                (0.0, 0.0, 0.0),
+                (1.0, 0.0, 0.0),
+                (1.0, 1.0, 0.0),
+                (0.0, 1.0, 0.0),
+                (0.5, 0.5, 0.0),
+                (1.5, 0.5, 0.0),
+                (1.5, 1.5, 0.0),
+                (0.5, 1.5, 0.0),
+            ],
+        )
+        .unwrap();
+        assert!(graph.is_self_intersecting());
+        let intersections = graph.self_intersections();
+        assert_eq!(1, intersections.len());
+        // Segments bound the overlap of the quadrilaterals.
+        let mut length = 0.0;
+        for (a, b) in intersections[0].segments.iter() {
+            for point in &[a, b] {
+                assert!(point.x >= 0.5 - 1e-9 && point.x <= 1.0 + 1e-9);
+                assert!(point.y >= 0.5 - 1e-9 && point.y <= 1.0 + 1e-9);
+                assert!(point.z.abs() < 1e-9);
+            }
+            length += (b - a).norm();
+        }
+        assert!((length - 2.0).abs() < 1e-9);
+
+        // The same quadrilaterals in distinct graphs.
+        let (left, right) = (
+            MeshGraph::<E3>::from_raw_buffers(
+                vec![Tetragon::new(0usize, 1, 2, 3)],
+                vec![
+                    (0.0, 0.0, 0.0),
+                    (1.0, 0.0, 0.0),
+                    (1.0, 1.0, 0.0),
+                    (0.0, 1.0, 0.0),
+                ],
+            )
+            .unwrap(),
+            MeshGraph::<E3>::from_raw_buffers(
+                vec![Tetragon::new(0usize, 1, 2, 3)],
+                vec![
+                    (0.5, 0.5, 0.0),
+                    (1.5, 0.5, 0.0),
+                    (1.5, 1.5, 0.0),
+                    (0.5, 1.5, 0.0),
+                ],
+            )
+            .unwrap(),
+        );
+        assert_eq!(1, left.intersections(&right).len());
+    }
+
+    #[test]
+    fn coplanar_adjacent_self_intersection() {
+        // A flat grid of quadrilaterals only meets at shared vertices and
+        // edges.
+        let graph = fixture::grid();
+        assert!(!graph.is_self_intersecting());
+    }
+}
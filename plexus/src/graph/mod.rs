@@ -245,20 +245,33 @@
 //! [`MeshGraph`]: crate::graph::MeshGraph
 //! [`UvSphere`]: crate::primitive::sphere::UvSphere
 
+mod atlas;
 mod builder;
 mod core;
 mod data;
+mod distance;
 mod edge;
 mod face;
+#[cfg(test)]
+mod fixture;
+mod geodesic;
 mod geometry;
+mod integral;
+mod intersection;
 mod mutation;
+mod parameterize;
 mod path;
+mod ray;
+mod repair;
+mod topology;
+mod validation;
 mod vertex;
+mod winding;
 
 use decorum::cmp::IntrinsicOrd;
-use decorum::R64;
+use decorum::{Real, R64};
 use itertools::Itertools;
-use num::{Integer, NumCast, ToPrimitive, Unsigned};
+use num::{Integer, NumCast, One, ToPrimitive, Unsigned, Zero};
 use smallvec::SmallVec;
 use std::borrow::Borrow;
 use std::collections::{HashMap, HashSet};
@@ -266,45 +279,58 @@ use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::iter::FromIterator;
+use std::mem;
 use std::vec;
 use theon::adjunct::{FromItems, Map};
-use theon::query::Aabb;
-use theon::space::{EuclideanSpace, Scalar};
+use theon::ops::{Cross, Dot};
+use theon::query::{Aabb, Ray};
+use theon::space::{EuclideanSpace, FiniteDimensional, InnerSpace, Scalar, Vector};
 use theon::{AsPosition, AsPositionMut};
 use thiserror::Error;
-use typenum::{self, NonZero};
+use typenum::{self, NonZero, U3};
 
 use crate::buffer::{BufferError, FromRawBuffers, FromRawBuffersWithArity, MeshBuffer};
 use crate::builder::{Buildable, FacetBuilder, MeshBuilder, SurfaceBuilder};
 use crate::encoding::{FaceDecoder, FromEncoding, VertexDecoder};
+use crate::entity::dijkstra;
 use crate::entity::storage::prelude::*;
 use crate::entity::storage::{AsStorage, AsStorageMut, AsStorageOf, Key, StorageTarget};
 use crate::entity::view::{Bind, Orphan, View};
 use crate::entity::EntityError;
-use crate::geometry::{FromGeometry, IntoGeometry};
+use crate::geometry::bvh::Bvh;
+use crate::geometry::tangent::{Tangent, TangentAccumulator};
+use crate::geometry::{FromGeometry, IntoGeometry, Metric};
 use crate::graph::builder::GraphBuilder;
 use crate::graph::core::{Core, OwnedCore};
 use crate::graph::data::Parametric;
 use crate::graph::edge::{Arc, Edge};
 use crate::graph::face::Face;
-use crate::graph::mutation::face::FaceInsertCache;
+use crate::graph::mutation::face::{FaceInsertCache, FaceReverseCache};
 use crate::graph::mutation::{Consistent, Immediate};
 use crate::graph::vertex::Vertex;
 use crate::index::{Flat, FromIndexer, Grouping, HashIndexer, IndexBuffer, IndexVertices, Indexer};
 use crate::primitive::decompose::IntoVertices;
 use crate::primitive::{IntoPolygons, Polygonal, UnboundedPolygon};
-use crate::transact::Transact;
-use crate::{DynamicArity, MeshArity, StaticArity};
+use crate::transact::{BypassOrCommit, Mutate, Transact};
+use crate::{DynamicArity, IteratorExt as _, MeshArity, StaticArity};
 
+pub use crate::entity::dijkstra::MetricTree;
 pub use crate::entity::view::{ClosedView, Rebind};
 pub use crate::graph::data::GraphData;
+pub use crate::graph::distance::{ClosestPoint, Feature};
 pub use crate::graph::edge::{ArcKey, ArcOrphan, ArcView, EdgeKey, EdgeOrphan, EdgeView, ToArc};
 pub use crate::graph::face::{FaceKey, FaceOrphan, FaceView, Ring, ToRing};
 pub use crate::graph::geometry::{
-    ArcNormal, EdgeMidpoint, FaceCentroid, FaceNormal, FacePlane, VertexCentroid, VertexNormal,
-    VertexPosition,
+    ArcNormal, Curvature, EdgeMidpoint, FaceArea, FaceCentroid, FaceNormal, FacePlane,
+    NormalWeight, VertexCentroid, VertexCurvature, VertexNormal, VertexPosition,
 };
+pub use crate::graph::intersection::FaceIntersection;
+pub use crate::graph::parameterize::{Parameterization, Pinning};
 pub use crate::graph::path::Path;
+pub use crate::graph::ray::RayHit;
+pub use crate::graph::repair::RepairReport;
+pub use crate::graph::topology::Invariants;
+pub use crate::graph::validation::{Defect, ValidationReport};
 pub use crate::graph::vertex::{VertexKey, VertexOrphan, VertexView};
 
 pub use Selector::ByIndex;
@@ -519,6 +545,66 @@ impl From<FaceKey> for GraphKey {
     }
 }
 
+/// Orientation of the faces of a [`MeshGraph`].
+///
+/// See [`MeshGraph::orient`].
+///
+/// [`MeshGraph`]: crate::graph::MeshGraph
+/// [`MeshGraph::orient`]: crate::graph::MeshGraph::orient
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Orientation {
+    /// The normals of closed sub-graphs point inward.
+    Inward,
+    /// The normals of closed sub-graphs point outward.
+    Outward,
+}
+
+/// Report of the orientation of the disjoint sub-graphs of a [`MeshGraph`].
+///
+/// Each sub-graph is identified by the key of one of its faces.
+///
+/// See [`MeshGraph::orient`].
+///
+/// [`MeshGraph`]: crate::graph::MeshGraph
+/// [`MeshGraph::orient`]: crate::graph::MeshGraph::orient
+#[derive(Clone, Debug, Default)]
+pub struct OrientationReport {
+    components: Vec<FaceKey>,
+    reversed: Vec<FaceKey>,
+    non_orientable: Vec<FaceKey>,
+    open: Vec<FaceKey>,
+}
+
+impl OrientationReport {
+    /// Gets a face from each disjoint sub-graph.
+    pub fn components(&self) -> &[FaceKey] {
+        self.components.as_slice()
+    }
+
+    /// Gets a face from each disjoint sub-graph that was reversed.
+    pub fn reversed(&self) -> &[FaceKey] {
+        self.reversed.as_slice()
+    }
+
+    /// Gets a face from each disjoint sub-graph that is non-orientable.
+    ///
+    /// A sub-graph is non-orientable if it was formed from input that could
+    /// not be wound consistently, such as a Möbius strip that was split when
+    /// the graph was constructed. This is only determined from a
+    /// [`RepairReport`].
+    ///
+    /// [`RepairReport`]: crate::graph::RepairReport
+    pub fn non_orientable(&self) -> &[FaceKey] {
+        self.non_orientable.as_slice()
+    }
+
+    /// Gets a face from each disjoint sub-graph that has a boundary and so
+    /// could not be oriented by volume.
+    pub fn open(&self) -> &[FaceKey] {
+        self.open.as_slice()
+    }
+}
+
 /// [Half-edge graph][dcel] representation of a polygonal mesh.
 ///
 /// `MeshGraph`s form a polygonal mesh from four interconnected entities:
@@ -728,6 +814,181 @@ where
         Path::bind(self, keys)
     }
 
+    /// Computes the least metrics from any of the given source vertices.
+    ///
+    /// The search stops when any of the destination vertices is reached. If
+    /// there are no destination vertices, then the metric of every reachable
+    /// vertex is computed. The output [`MetricTree`] maps each visited vertex
+    /// to its metric and the preceding vertex on a least path from the
+    /// nearest source and can be reused by callers to extract paths.
+    ///
+    /// See [`vertex_metrics_with_heuristic`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a vertex is not found or if the metric is negative.
+    ///
+    /// [`MetricTree`]: crate::graph::MetricTree
+    /// [`vertex_metrics_with_heuristic`]: crate::graph::MeshGraph::vertex_metrics_with_heuristic
+    pub fn vertex_metrics_with<I, J, Q, F>(
+        &self,
+        from: I,
+        to: J,
+        f: F,
+    ) -> Result<MetricTree<VertexKey, Q>, GraphError>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<VertexKey>,
+        J: IntoIterator,
+        J::Item: Borrow<VertexKey>,
+        Q: Copy + Metric,
+        F: Fn(VertexView<&Self>, VertexView<&Self>) -> Q,
+    {
+        self.vertex_metrics_with_heuristic(from, to, f, |_| Q::zero())
+    }
+
+    /// Computes the least metrics from any of the given source vertices using
+    /// A* search.
+    ///
+    /// The heuristic estimates the metric from a vertex to the nearest
+    /// destination vertex and must never overestimate it. For example, if the
+    /// metric is the Euclidean distance between vertices, then the least
+    /// Euclidean distance to any destination vertex is an appropriate
+    /// heuristic. Metrics in the output [`MetricTree`] do not include the
+    /// heuristic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a vertex is not found or if the metric is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// # extern crate theon;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::Point3;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::generate::Position;
+    /// use plexus::primitive::sphere::UvSphere;
+    /// use theon::space::InnerSpace;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// let graph: MeshGraph<E3> = UvSphere::new(16, 8).polygons::<Position<E3>>().collect();
+    /// let from = graph
+    ///     .vertices()
+    ///     .take(2)
+    ///     .map(|vertex| vertex.key())
+    ///     .collect::<Vec<_>>();
+    /// let to = graph.vertices().nth(8).unwrap();
+    /// let position = *to.position();
+    /// let metrics = graph
+    ///     .vertex_metrics_with_heuristic(
+    ///         &from,
+    ///         Some(to.key()),
+    ///         |from, to| (*to.position() - *from.position()).magnitude(),
+    ///         |vertex| (position - *vertex.position()).magnitude(),
+    ///     )
+    ///     .unwrap();
+    /// assert!(metrics.contains_key(&to.key()));
+    /// ```
+    ///
+    /// [`MetricTree`]: crate::graph::MetricTree
+    pub fn vertex_metrics_with_heuristic<I, J, Q, F, H>(
+        &self,
+        from: I,
+        to: J,
+        f: F,
+        h: H,
+    ) -> Result<MetricTree<VertexKey, Q>, GraphError>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<VertexKey>,
+        J: IntoIterator,
+        J::Item: Borrow<VertexKey>,
+        Q: Copy + Metric,
+        F: Fn(VertexView<&Self>, VertexView<&Self>) -> Q,
+        H: Fn(VertexView<&Self>) -> Q,
+    {
+        dijkstra::search_with(
+            self,
+            from.into_iter().map(|key| *key.borrow()),
+            to.into_iter().map(|key| *key.borrow()),
+            f,
+            h,
+        )
+        .map_err(From::from)
+    }
+
+    /// Computes the least metrics from any of the given source faces.
+    ///
+    /// Faces are adjacent if they share an edge. This function behaves like
+    /// [`vertex_metrics_with`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a face is not found or if the metric is negative.
+    ///
+    /// [`vertex_metrics_with`]: crate::graph::MeshGraph::vertex_metrics_with
+    pub fn face_metrics_with<I, J, Q, F>(
+        &self,
+        from: I,
+        to: J,
+        f: F,
+    ) -> Result<MetricTree<FaceKey, Q>, GraphError>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<FaceKey>,
+        J: IntoIterator,
+        J::Item: Borrow<FaceKey>,
+        Q: Copy + Metric,
+        F: Fn(FaceView<&Self>, FaceView<&Self>) -> Q,
+    {
+        self.face_metrics_with_heuristic(from, to, f, |_| Q::zero())
+    }
+
+    /// Computes the least metrics from any of the given source faces using A*
+    /// search.
+    ///
+    /// Faces are adjacent if they share an edge. This function behaves like
+    /// [`vertex_metrics_with_heuristic`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a face is not found or if the metric is negative.
+    ///
+    /// [`vertex_metrics_with_heuristic`]: crate::graph::MeshGraph::vertex_metrics_with_heuristic
+    pub fn face_metrics_with_heuristic<I, J, Q, F, H>(
+        &self,
+        from: I,
+        to: J,
+        f: F,
+        h: H,
+    ) -> Result<MetricTree<FaceKey, Q>, GraphError>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<FaceKey>,
+        J: IntoIterator,
+        J::Item: Borrow<FaceKey>,
+        Q: Copy + Metric,
+        F: Fn(FaceView<&Self>, FaceView<&Self>) -> Q,
+        H: Fn(FaceView<&Self>) -> Q,
+    {
+        dijkstra::search_with(
+            self,
+            from.into_iter().map(|key| *key.borrow()),
+            to.into_iter().map(|key| *key.borrow()),
+            f,
+            h,
+        )
+        .map_err(From::from)
+    }
+
     /// Gets an axis-aligned bounding box that encloses the graph.
     pub fn aabb(&self) -> Aabb<VertexPosition<G>>
     where
@@ -738,185 +999,1889 @@ where
         Aabb::from_points(self.vertices().map(|vertex| *vertex.position()))
     }
 
-    // TODO: This triangulation does not consider geometry and exhibits some
-    //       bad behavior in certain situations. Triangulation needs to be
-    //       reworked and may need to expose a bit more complexity. A geometric
-    //       triangulation algorithm would be a useful addition and could
-    //       detect concave faces and provide more optimal splits. See comments
-    //       on `FaceView::triangulate`.
-    /// Triangulates the graph, tessellating all faces into triangles.
-    pub fn triangulate(&mut self) {
-        // TODO: This implementation is a bit fragile and depends on the
-        //       semantics of `TopologyConflict` in this context. It also panics
-        //       if no valid split is found given all offsets or if some other
-        //       error is encountered while splitting. Can this code assume that
-        //       any of these conditions aren't possible? This should work a bit
-        //       better than using `FaceView::triangulate` until triangulation
-        //       is reworked.
-        let keys = self
-            .core
-            .faces
-            .iter()
-            .map(|(key, _)| key)
-            .collect::<Vec<_>>();
-        for key in keys {
-            let mut face = self.face_mut(key).unwrap();
-            let mut offset = 0;
-            while face.arity() > 3 {
-                match face.split(ByIndex(offset), ByIndex(offset + 2)) {
-                    Ok(next) => {
-                        face = next.into_face().expect_consistent();
-                        offset = 0;
-                    }
-                    Err(GraphError::TopologyConflict) => {
-                        // Retry if the split intersected another face. See
-                        // `FaceSplitCache::from_face`.
-                        face = self.face_mut(key).unwrap();
-                        offset += 1;
-                        if offset >= face.arity() {
-                            panic!()
-                        }
-                    }
-                    _ => panic!(),
-                }
-            }
-        }
+    /// Builds a bounding volume hierarchy over the faces of the graph.
+    ///
+    /// The hierarchy refers to faces by key. It should be refit via
+    /// [`MeshGraph::refit_bvh`] when positions change and rebuilt when faces
+    /// are inserted or removed.
+    ///
+    /// See [`Bvh`].
+    ///
+    /// [`Bvh`]: crate::geometry::bvh::Bvh
+    /// [`MeshGraph::refit_bvh`]: crate::graph::MeshGraph::refit_bvh
+    pub fn bvh(&self) -> Bvh<FaceKey, VertexPosition<G>>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    {
+        self.faces()
+            .map(|face| {
+                let positions = face
+                    .adjacent_vertices()
+                    .map(|vertex| *vertex.position())
+                    .collect::<SmallVec<[_; 8]>>();
+                (face.key(), positions)
+            })
+            .collect()
     }
 
-    /// Smooths the positions of vertices in the graph.
+    /// Gets the closest intersection of a ray with the faces of the graph.
     ///
-    /// Each position is translated by its offset from its centroid scaled by
-    /// the given factor. The centroid of a vertex position is the mean of the
-    /// positions of its adjacent vertices. That is, given a factor $k$ and a
-    /// vertex with position $P$ and centroid $Q$, its position becomes
-    /// $P+k(Q-P)$.
-    pub fn smooth<T>(&mut self, factor: T)
+    /// Faces are intersected as triangulations of their rings, so concave
+    /// faces are supported. Both sides of faces are intersected. Returns
+    /// `None` if the ray does not intersect any face.
+    ///
+    /// # Examples
+    ///
+    /// Picking a face of a cube:
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::Point3;
+    /// use plexus::geometry::{Ray, Unit};
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::cube::Cube;
+    /// use plexus::primitive::generate::Position;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// let graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+    /// let hit = graph
+    ///     .cast_ray(&Ray {
+    ///         origin: E3::new(0.0.into(), 0.0.into(), 2.0.into()),
+    ///         direction: -Unit::z(),
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(R64::from(1.5), hit.distance);
+    /// assert_eq!(4, hit.coordinates.len());
+    /// ```
+    pub fn cast_ray(&self, ray: &Ray<VertexPosition<G>>) -> Option<RayHit<VertexPosition<G>>>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+    {
+        self.cast_ray_all(ray).into_iter().next()
+    }
+
+    /// Gets all intersections of a ray with the faces of the graph sorted by
+    /// distance.
+    ///
+    /// See [`MeshGraph::cast_ray`].
+    ///
+    /// [`MeshGraph::cast_ray`]: crate::graph::MeshGraph::cast_ray
+    pub fn cast_ray_all(&self, ray: &Ray<VertexPosition<G>>) -> Vec<RayHit<VertexPosition<G>>>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+    {
+        ray::cast(self, None, ray).expect_consistent()
+    }
+
+    /// Gets the closest intersection of a ray with the faces of the graph
+    /// using a bounding volume hierarchy.
+    ///
+    /// Only faces with bounds that are intersected by the ray are tested. See
+    /// [`MeshGraph::bvh`] and [`MeshGraph::cast_ray`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the hierarchy refers to faces that are not in the
+    /// graph.
+    ///
+    /// [`MeshGraph::bvh`]: crate::graph::MeshGraph::bvh
+    /// [`MeshGraph::cast_ray`]: crate::graph::MeshGraph::cast_ray
+    pub fn cast_ray_by_bvh(
+        &self,
+        bvh: &Bvh<FaceKey, VertexPosition<G>>,
+        ray: &Ray<VertexPosition<G>>,
+    ) -> Result<Option<RayHit<VertexPosition<G>>>, GraphError>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+    {
+        self.cast_ray_all_by_bvh(bvh, ray)
+            .map(|hits| hits.into_iter().next())
+    }
+
+    /// Gets all intersections of a ray with the faces of the graph sorted by
+    /// distance using a bounding volume hierarchy.
+    ///
+    /// See [`MeshGraph::cast_ray_by_bvh`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the hierarchy refers to faces that are not in the
+    /// graph.
+    ///
+    /// [`MeshGraph::cast_ray_by_bvh`]: crate::graph::MeshGraph::cast_ray_by_bvh
+    pub fn cast_ray_all_by_bvh(
+        &self,
+        bvh: &Bvh<FaceKey, VertexPosition<G>>,
+        ray: &Ray<VertexPosition<G>>,
+    ) -> Result<Vec<RayHit<VertexPosition<G>>>, GraphError>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+    {
+        ray::cast(self, Some(bvh), ray)
+    }
+
+    /// Gets the point on the surface of the graph closest to the given point.
+    ///
+    /// Faces are treated as triangulations of their rings. Returns `None` if
+    /// the graph has no faces.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::Point3;
+    /// use plexus::graph::{Feature, MeshGraph};
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::cube::Cube;
+    /// use plexus::primitive::generate::Position;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// let graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+    /// let closest = graph
+    ///     .closest_point(E3::new(1.0.into(), 1.0.into(), 1.0.into()))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     E3::new(0.5.into(), 0.5.into(), 0.5.into()),
+    ///     closest.position
+    /// );
+    /// if let Feature::Vertex(key) = closest.feature {
+    ///     assert_eq!(closest.position, *graph.vertex(key).unwrap().position());
+    /// }
+    /// ```
+    pub fn closest_point(&self, point: VertexPosition<G>) -> Option<ClosestPoint<VertexPosition<G>>>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+    {
+        distance::closest_point(self, None, point).expect_consistent()
+    }
+
+    /// Gets the point on the surface of the graph closest to the given point
+    /// using a bounding volume hierarchy.
+    ///
+    /// See [`MeshGraph::bvh`] and [`MeshGraph::closest_point`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the hierarchy refers to faces that are not in the
+    /// graph.
+    ///
+    /// [`MeshGraph::bvh`]: crate::graph::MeshGraph::bvh
+    /// [`MeshGraph::closest_point`]: crate::graph::MeshGraph::closest_point
+    pub fn closest_point_by_bvh(
+        &self,
+        bvh: &Bvh<FaceKey, VertexPosition<G>>,
+        point: VertexPosition<G>,
+    ) -> Result<Option<ClosestPoint<VertexPosition<G>>>, GraphError>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+    {
+        distance::closest_point(self, Some(bvh), point)
+    }
+
+    /// Gets the signed distance from the surface of the graph to the given
+    /// point.
+    ///
+    /// The distance is negative if the point is inside of the surface. The
+    /// sign is determined by the angle-weighted pseudo-normal of the feature
+    /// on which the closest point lies and is only meaningful if the graph is
+    /// closed and consistently oriented with outward facing normals.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the graph is not closed or has no faces.
+    pub fn signed_distance(
+        &self,
+        point: VertexPosition<G>,
+    ) -> Result<Scalar<VertexPosition<G>>, GraphError>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+    {
+        distance::signed_distance(self, None, point)
+    }
+
+    /// Gets the signed distance from the surface of the graph to the given
+    /// point using a bounding volume hierarchy.
+    ///
+    /// See [`MeshGraph::bvh`] and [`MeshGraph::signed_distance`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the graph is not closed or has no faces or if the
+    /// hierarchy refers to faces that are not in the graph.
+    ///
+    /// [`MeshGraph::bvh`]: crate::graph::MeshGraph::bvh
+    /// [`MeshGraph::signed_distance`]: crate::graph::MeshGraph::signed_distance
+    pub fn signed_distance_by_bvh(
+        &self,
+        bvh: &Bvh<FaceKey, VertexPosition<G>>,
+        point: VertexPosition<G>,
+    ) -> Result<Scalar<VertexPosition<G>>, GraphError>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+    {
+        distance::signed_distance(self, Some(bvh), point)
+    }
+
+    /// Gets the generalized winding number of a point with respect to the
+    /// graph.
+    ///
+    /// For a closed and consistently oriented graph with outward facing
+    /// normals, the winding number is one for points inside of the graph and
+    /// zero for points outside of the graph. The winding number degrades
+    /// gracefully for graphs that are open or self-intersecting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::Point3;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::cube::Cube;
+    /// use plexus::primitive::generate::Position;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// let graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+    /// let winding = graph.winding_number(E3::origin());
+    ///
+    /// assert!(winding > 0.5.into());
+    /// assert!(graph.contains_point(E3::origin()));
+    /// ```
+    pub fn winding_number(&self, point: VertexPosition<G>) -> Scalar<VertexPosition<G>>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+    {
+        winding::winding_number(&winding::triangles(self), point)
+    }
+
+    /// Gets the generalized winding numbers of points with respect to the
+    /// graph.
+    ///
+    /// The triangulations of the faces of the graph are computed once and
+    /// shared by all of the points. See [`MeshGraph::winding_number`].
+    ///
+    /// [`MeshGraph::winding_number`]: crate::graph::MeshGraph::winding_number
+    pub fn winding_numbers<I>(&self, points: I) -> Vec<Scalar<VertexPosition<G>>>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+        I: IntoIterator<Item = VertexPosition<G>>,
+    {
+        let triangles = winding::triangles(self);
+        points
+            .into_iter()
+            .map(|point| winding::winding_number(&triangles, point))
+            .collect()
+    }
+
+    /// Returns `true` if the point is inside of the graph.
+    ///
+    /// A point is inside of the graph if its winding number is greater than
+    /// one half. See [`MeshGraph::winding_number`].
+    ///
+    /// [`MeshGraph::winding_number`]: crate::graph::MeshGraph::winding_number
+    pub fn contains_point(&self, point: VertexPosition<G>) -> bool
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+    {
+        winding::is_inside(self.winding_number(point))
+    }
+
+    /// Determines which points are inside of the graph.
+    ///
+    /// The triangulations of the faces of the graph are computed once and
+    /// shared by all of the points. See [`MeshGraph::contains_point`].
+    ///
+    /// [`MeshGraph::contains_point`]: crate::graph::MeshGraph::contains_point
+    pub fn contains_points<I>(&self, points: I) -> Vec<bool>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+        I: IntoIterator<Item = VertexPosition<G>>,
+    {
+        self.winding_numbers(points)
+            .into_iter()
+            .map(winding::is_inside)
+            .collect()
+    }
+
+    /// Gets the intersections between the faces of the graph.
+    ///
+    /// Faces are intersected as triangulations of their rings and coplanar
+    /// faces intersect where they overlap. Adjacent faces are intersected,
+    /// but the vertices and edges that they share are not considered
+    /// intersections. Each pair of intersecting faces is reported once.
+    pub fn self_intersections(&self) -> Vec<FaceIntersection<VertexPosition<G>>>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+    {
+        intersection::self_intersections(self)
+    }
+
+    /// Returns `true` if any faces of the graph intersect.
+    ///
+    /// See [`MeshGraph::self_intersections`].
+    ///
+    /// # Examples
+    ///
+    /// Rejecting a graph with adjacent faces that fold over one another:
+    ///
+    /// ```rust
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use nalgebra::Point3;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::Tetragon;
+    ///
+    /// type E3 = Point3<f64>;
+    ///
+    /// let graph = MeshGraph::<E3>::from_raw_buffers(
+    ///     vec![Tetragon::new(0usize, 1, 2, 3), Tetragon::new(0, 3, 4, 5)],
+    ///     vec![
+    ///         (0.0, 0.0, 0.0),
+    ///         (1.0, 0.0, 0.0),
+    ///         (1.0, 1.0, 0.0),
+    ///         (0.0, 1.0, 0.0),
+    ///         (0.6, 0.9, -0.5),
+    ///         (0.6, 0.1, 0.5),
+    ///     ],
+    /// )
+    /// .unwrap();
+    ///
+    /// assert!(graph.is_self_intersecting());
+    /// ```
+    ///
+    /// [`MeshGraph::self_intersections`]: crate::graph::MeshGraph::self_intersections
+    pub fn is_self_intersecting(&self) -> bool
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+    {
+        !self.self_intersections().is_empty()
+    }
+
+    /// Gets the intersections between the faces of the graph and the faces of
+    /// another graph.
+    ///
+    /// The first key of each pair of faces refers to a face in this graph and
+    /// the second key refers to a face in the other graph. Faces are
+    /// intersected as triangulations of their rings and coplanar faces
+    /// intersect where they overlap.
+    pub fn intersections<H>(&self, other: &MeshGraph<H>) -> Vec<FaceIntersection<VertexPosition<G>>>
+    where
+        G::Vertex: AsPosition,
+        H: GraphData,
+        H::Vertex: AsPosition<Position = VertexPosition<G>>,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+    {
+        intersection::intersections(self, other)
+    }
+
+    /// Refits a bounding volume hierarchy to the positions of the faces in
+    /// the graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the faces in the hierarchy differ from the faces in
+    /// the graph. In that case, the hierarchy must be rebuilt via
+    /// [`MeshGraph::bvh`].
+    ///
+    /// [`MeshGraph::bvh`]: crate::graph::MeshGraph::bvh
+    pub fn refit_bvh(&self, bvh: &mut Bvh<FaceKey, VertexPosition<G>>) -> Result<(), GraphError>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    {
+        if bvh.len() != self.face_count() || bvh.keys().any(|key| self.face(*key).is_none()) {
+            return Err(GraphError::TopologyConflict);
+        }
+        bvh.refit_with(|key| {
+            self.face(*key)
+                .expect_consistent()
+                .adjacent_vertices()
+                .map(|vertex| *vertex.position())
+                .collect::<SmallVec<[_; 8]>>()
+        });
+        Ok(())
+    }
+
+    /// Gets the total area of the faces in the graph.
+    ///
+    /// See [`FaceView::area`].
+    ///
+    /// [`FaceView::area`]: crate::graph::FaceView::area
+    pub fn surface_area(&self) -> Scalar<VertexPosition<G>>
+    where
+        G: FaceArea,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+    {
+        self.faces()
+            .map(|face| face.area())
+            .fold(Zero::zero(), |sum, area| sum + area)
+    }
+
+    /// Gets the signed volume enclosed by the graph.
+    ///
+    /// The volume is positive if the faces of the graph are oriented outward
+    /// and negative if they are oriented inward (see [`orient`]). The
+    /// volume is only meaningful if the graph is closed. Faces are decomposed
+    /// into a fan of triangles about their centroids, and so non-planar faces
+    /// are supported.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::Point3;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::cube::Cube;
+    /// use plexus::primitive::generate::Position;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// // The cube has unit width and its faces are oriented outward.
+    /// let graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+    /// let area = graph.surface_area();
+    /// let volume = graph.volume();
+    ///
+    /// assert!((area.into_inner() - 6.0).abs() < 1e-9);
+    /// assert!((volume.into_inner() - 1.0).abs() < 1e-9);
+    /// ```
+    ///
+    /// [`orient`]: crate::graph::MeshGraph::orient
+    pub fn volume(&self) -> Scalar<VertexPosition<G>>
+    where
+        G: FaceCentroid,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    {
+        integral::moments(self.faces()).volume
+    }
+
+    /// Gets the center of mass of the solid enclosed by the graph.
+    ///
+    /// The solid is assumed to have uniform density. Returns `None` if the
+    /// volume of the graph is zero. See [`volume`].
+    ///
+    /// [`volume`]: crate::graph::MeshGraph::volume
+    pub fn center_of_mass(&self) -> Option<VertexPosition<G>>
+    where
+        G: FaceCentroid,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    {
+        integral::moments(self.faces())
+            .center()
+            .map(|[x, y, z]| EuclideanSpace::from_xyz(x, y, z))
+    }
+
+    /// Gets the inertia tensor of the solid enclosed by the graph about its
+    /// center of mass.
+    ///
+    /// The solid is assumed to have uniform unit density, so the tensor should
+    /// be scaled by the density of the solid. The tensor is given as rows of a
+    /// symmetric $3\times3$ matrix. Returns `None` if the volume of the graph
+    /// is zero. See [`volume`].
+    ///
+    /// [`volume`]: crate::graph::MeshGraph::volume
+    pub fn inertia_tensor(&self) -> Option<[[Scalar<VertexPosition<G>>; 3]; 3]>
+    where
+        G: FaceCentroid,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    {
+        integral::moments(self.faces()).inertia()
+    }
+
+    /// Computes the geodesic distance from the nearest of the given source
+    /// vertices to each vertex in the graph.
+    ///
+    /// Distances are measured over the surface of the graph rather than along
+    /// its edges. Unlike [`VertexView::shortest_path`], paths may cross faces,
+    /// and so distances on triangulated surfaces do not zig-zag along edges
+    /// and are not overestimated. Distances are computed using the fast
+    /// marching method. Faces with more than three edges are unfolded at each
+    /// corner and should be convex and approximately planar.
+    ///
+    /// Vertices that are not reachable from any source are not included in
+    /// the output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a source vertex is not found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// # extern crate theon;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::Point3;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::generate::Position;
+    /// use plexus::primitive::sphere::UvSphere;
+    /// use std::f64::consts::PI;
+    /// use theon::space::InnerSpace;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// let graph: MeshGraph<E3> = UvSphere::new(16, 8).polygons::<Position<E3>>().collect();
+    /// let source = graph.vertices().nth(0).unwrap();
+    /// let distances = graph.geodesic_distances(Some(source.key())).unwrap();
+    /// assert_eq!(R64::from(0.0), distances[&source.key()]);
+    ///
+    /// // The distance to the farthest vertex of the unit sphere is no less than
+    /// // the Euclidean distance and is approximately half of the circumference.
+    /// let (euclidean, geodesic) = graph
+    ///     .vertices()
+    ///     .map(|vertex| {
+    ///         let euclidean = (*vertex.position() - *source.position()).magnitude();
+    ///         (euclidean, distances[&vertex.key()])
+    ///     })
+    ///     .max()
+    ///     .unwrap();
+    /// assert!(geodesic >= euclidean);
+    /// assert!(geodesic < R64::from(1.25 * PI));
+    /// ```
+    ///
+    /// [`VertexView::shortest_path`]: crate::graph::VertexView::shortest_path
+    pub fn geodesic_distances<I>(
+        &self,
+        sources: I,
+    ) -> Result<HashMap<VertexKey, Scalar<VertexPosition<G>>>, GraphError>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+        I: IntoIterator,
+        I::Item: Borrow<VertexKey>,
+    {
+        let sources = sources
+            .into_iter()
+            .map(|key| *key.borrow())
+            .collect::<Vec<_>>();
+        geodesic::distances(self, &sources)
+    }
+
+    /// Computes planar (UV) coordinates for each vertex in the graph.
+    ///
+    /// The graph must be topologically equivalent to a disk: it must be
+    /// connected, orientable, have genus zero, and have exactly one boundary.
+    /// Closed graphs must be cut open before they can be parameterized. The
+    /// boundary is pinned according to the given [`Pinning`] and the interior
+    /// is computed using the given [`Parameterization`] method.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the graph is not equivalent to a disk, if the
+    /// pinning is not supported by the method, or if the geometry of the graph
+    /// is degenerate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use nalgebra::Point3;
+    /// use plexus::graph::{MeshGraph, Parameterization, Pinning};
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::Tetragon;
+    ///
+    /// let graph = MeshGraph::<Point3<f64>>::from_raw_buffers(
+    ///     vec![Tetragon::new(0usize, 1, 3, 2), Tetragon::new(2, 3, 5, 4)],
+    ///     vec![
+    ///         (0.0, 0.0, 0.0),
+    ///         (1.0, 0.0, 0.0),
+    ///         (0.0, 1.0, 0.5),
+    ///         (1.0, 1.0, 0.5),
+    ///         (0.0, 2.0, 0.0),
+    ///         (1.0, 2.0, 0.0),
+    ///     ],
+    /// )
+    /// .unwrap();
+    /// let uvs = graph
+    ///     .parameterize(Parameterization::Conformal, Pinning::Extrema)
+    ///     .unwrap();
+    /// ```
+    ///
+    /// [`Parameterization`]: crate::graph::Parameterization
+    /// [`Pinning`]: crate::graph::Pinning
+    pub fn parameterize(
+        &self,
+        method: Parameterization,
+        pinning: Pinning,
+    ) -> Result<
+        HashMap<VertexKey, (Scalar<VertexPosition<G>>, Scalar<VertexPosition<G>>)>,
+        GraphError,
+    >
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+    {
+        let mut vertices = self.disjoint_subgraph_vertices();
+        if vertices.len() != 1 {
+            return Err(GraphError::TopologyMalformed);
+        }
+        let key = vertices.next().expect_consistent().key();
+        parameterize::parameterize(self, key, method, pinning)
+    }
+
+    /// Computes planar (UV) coordinates for each vertex in the graph and
+    /// writes them into vertex data using the given function.
+    ///
+    /// See [`parameterize`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the graph could not be parameterized. If an error
+    /// is returned, then no vertex data is modified.
+    ///
+    /// [`parameterize`]: crate::graph::MeshGraph::parameterize
+    pub fn parameterize_with<F>(
+        &mut self,
+        method: Parameterization,
+        pinning: Pinning,
+        mut f: F,
+    ) -> Result<(), GraphError>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+        F: FnMut(&mut G::Vertex, (Scalar<VertexPosition<G>>, Scalar<VertexPosition<G>>)),
+    {
+        let mut uvs = self.parameterize(method, pinning)?;
+        for mut vertex in self.vertex_orphans() {
+            if let Some(uv) = uvs.remove(&vertex.key()) {
+                f(vertex.get_mut(), uv);
+            }
+        }
+        Ok(())
+    }
+
+    /// Selects seam edges that cut each disjoint sub-graph into a disk.
+    ///
+    /// The seams of a sub-graph are the edges that are not crossed by a
+    /// spanning tree of its faces (a cut graph). Branches that do not form
+    /// loops are pruned, because cutting along them does not change the
+    /// topology of the sub-graph, except that closed sub-graphs with genus
+    /// zero retain a path of two edges. Boundary edges are never seams. The
+    /// seams can be passed to [`cut`] to prepare a closed graph for
+    /// [`parameterize`].
+    ///
+    /// [`cut`]: crate::graph::MeshGraph::cut
+    /// [`parameterize`]: crate::graph::MeshGraph::parameterize
+    pub fn seams(&self) -> Vec<EdgeKey> {
+        atlas::seams(self)
+    }
+
+    /// Cuts the graph along the given seam edges.
+    ///
+    /// Vertices along seams are copied so that each seam becomes a pair of
+    /// boundaries. Vertex and face data are cloned into the cut graph, but
+    /// arc and edge data are not preserved. Returns the cut graph and a
+    /// mapping from each of its vertices to the vertex in this graph from
+    /// which it was copied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a seam edge is not found.
+    ///
+    /// # Examples
+    ///
+    /// Cutting a cube into a disk and packing it into an atlas:
+    ///
+    /// ```rust
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use nalgebra::Point3;
+    /// use plexus::graph::{MeshGraph, Parameterization, Pinning};
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::cube::Cube;
+    /// use plexus::primitive::generate::Position;
+    ///
+    /// type E3 = Point3<f64>;
+    ///
+    /// let graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+    /// let (graph, _) = graph.cut(graph.seams()).unwrap();
+    /// let uvs = graph
+    ///     .atlas(Parameterization::Conformal, Pinning::Extrema, 0.01)
+    ///     .unwrap();
+    /// ```
+    pub fn cut<I>(&self, edges: I) -> Result<(Self, HashMap<VertexKey, VertexKey>), GraphError>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<EdgeKey>,
+    {
+        let seams = edges
+            .into_iter()
+            .map(|key| *key.borrow())
+            .collect::<HashSet<_>>();
+        atlas::cut(self, &seams)
+    }
+
+    /// Computes planar (UV) coordinates for each vertex in the graph and packs
+    /// each disjoint sub-graph as a chart into the unit square.
+    ///
+    /// Each disjoint sub-graph is parameterized as in [`parameterize`] and so
+    /// must be topologically equivalent to a disk. Charts are scaled to a
+    /// uniform density with respect to surface area and are packed into rows.
+    /// The padding is the approximate distance between charts in the unit
+    /// square. Sub-graphs without faces are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a disjoint sub-graph could not be parameterized.
+    ///
+    /// [`parameterize`]: crate::graph::MeshGraph::parameterize
+    pub fn atlas<T>(
+        &self,
+        method: Parameterization,
+        pinning: Pinning,
+        padding: T,
+    ) -> Result<
+        HashMap<VertexKey, (Scalar<VertexPosition<G>>, Scalar<VertexPosition<G>>)>,
+        GraphError,
+    >
+    where
+        T: Into<Scalar<VertexPosition<G>>>,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+    {
+        atlas::pack(self, method, pinning, padding.into())
+    }
+
+    // TODO: This triangulation does not consider geometry and exhibits some
+    //       bad behavior in certain situations. Triangulation needs to be
+    //       reworked and may need to expose a bit more complexity. A geometric
+    //       triangulation algorithm would be a useful addition and could
+    //       detect concave faces and provide more optimal splits. See comments
+    //       on `FaceView::triangulate`.
+    /// Triangulates the graph, tessellating all faces into triangles.
+    pub fn triangulate(&mut self) {
+        // TODO: This implementation is a bit fragile and depends on the
+        //       semantics of `TopologyConflict` in this context. It also panics
+        //       if no valid split is found given all offsets or if some other
+        //       error is encountered while splitting. Can this code assume that
+        //       any of these conditions aren't possible? This should work a bit
+        //       better than using `FaceView::triangulate` until triangulation
+        //       is reworked.
+        let keys = self
+            .core
+            .faces
+            .iter()
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>();
+        for key in keys {
+            let mut face = self.face_mut(key).unwrap();
+            let mut offset = 0;
+            while face.arity() > 3 {
+                match face.split(ByIndex(offset), ByIndex(offset + 2)) {
+                    Ok(next) => {
+                        face = next.into_face().expect_consistent();
+                        offset = 0;
+                    }
+                    Err(GraphError::TopologyConflict) => {
+                        // Retry if the split intersected another face. See
+                        // `FaceSplitCache::from_face`.
+                        face = self.face_mut(key).unwrap();
+                        offset += 1;
+                        if offset >= face.arity() {
+                            panic!()
+                        }
+                    }
+                    _ => panic!(),
+                }
+            }
+        }
+    }
+
+    /// Computes the curvature at each vertex in the graph.
+    ///
+    /// See [`VertexCurvature`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the curvature at any vertex cannot be computed,
+    /// such as when a vertex has no faces or is adjacent to degenerate faces.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::Point3;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::generate::Position;
+    /// use plexus::primitive::sphere::UvSphere;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// let mut graph: MeshGraph<E3> = UvSphere::new(16, 8).polygons::<Position<E3>>().collect();
+    /// graph.triangulate();
+    ///
+    /// let curvatures = graph.vertex_curvatures().unwrap();
+    /// ```
+    ///
+    /// [`VertexCurvature`]: crate::graph::VertexCurvature
+    pub fn vertex_curvatures(
+        &self,
+    ) -> Result<HashMap<VertexKey, Curvature<VertexPosition<G>>>, GraphError>
+    where
+        G: VertexCurvature,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+    {
+        self.vertices()
+            .map(|vertex| {
+                vertex
+                    .curvature()
+                    .map(|curvature| (vertex.key(), curvature))
+            })
+            .collect()
+    }
+
+    /// Computes the normal of each vertex in the graph using the given
+    /// weighting of the normals of adjacent faces.
+    ///
+    /// The normal of each face is computed only once, so this is more
+    /// efficient than computing the normal of each vertex individually. This
+    /// can be used to construct buffers with vertex normals.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the normal of any face or vertex cannot be
+    /// computed, such as when a face is degenerate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::{Point3, Vector3};
+    /// use plexus::buffer::MeshBuffer3;
+    /// use plexus::graph::{MeshGraph, NormalWeight};
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::generate::Position;
+    /// use plexus::primitive::sphere::UvSphere;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// let mut graph: MeshGraph<E3> = UvSphere::new(16, 8).polygons::<Position<E3>>().collect();
+    /// graph.triangulate();
+    ///
+    /// let normals = graph.vertex_normals(NormalWeight::Angle).unwrap();
+    /// let buffer: MeshBuffer3<usize, (E3, Vector3<R64>)> = graph
+    ///     .to_mesh_by_vertex_with(|vertex| (*vertex.position(), normals[&vertex.key()]))
+    ///     .unwrap();
+    /// ```
+    pub fn vertex_normals(
+        &self,
+        weight: NormalWeight,
+    ) -> Result<HashMap<VertexKey, Vector<VertexPosition<G>>>, GraphError>
+    where
+        G: FaceArea + FaceNormal,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+    {
+        let normals = self
+            .faces()
+            .map(|face| face.normal().map(|normal| (face.key(), normal)))
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        self.vertices()
+            .map(|vertex| {
+                geometry::vertex_normal_with(vertex, weight, |face| Ok(normals[&face.key()]))
+                    .map(|normal| (vertex.key(), normal))
+            })
+            .collect()
+    }
+
+    /// Computes a tangent for each corner of each face in the graph.
+    ///
+    /// The given function returns the normal and texture coordinates of a
+    /// vertex in a face. Tangents are computed in the manner of MikkTSpace:
+    /// the tangents of corners about a vertex that share a normal, texture
+    /// coordinates, and handedness are weighted by angle and averaged, while
+    /// corners with distinct normals or texture coordinates (such as along
+    /// seams or creases) or with mirrored texture coordinates are computed
+    /// independently. The output is keyed by face and vertex and so is well
+    /// suited to [`to_mesh_by_face_with`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a tangent cannot be computed, such as when a normal
+    /// is zero or the faces about a vertex are degenerate.
+    ///
+    /// [`to_mesh_by_face_with`]: crate::graph::MeshGraph::to_mesh_by_face_with
+    pub fn corner_tangents_with<F>(
+        &self,
+        mut f: F,
+    ) -> Result<HashMap<(FaceKey, VertexKey), Tangent<Vector<VertexPosition<G>>>>, GraphError>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>> + PartialEq,
+        F: FnMut(
+            FaceView<&Self>,
+            VertexView<&Self>,
+        ) -> (
+            Vector<VertexPosition<G>>,
+            (Scalar<VertexPosition<G>>, Scalar<VertexPosition<G>>),
+        ),
+    {
+        let mut attributes = HashMap::new();
+        for face in self.faces() {
+            for vertex in face.adjacent_vertices() {
+                attributes.insert((face.key(), vertex.key()), f(face, vertex));
+            }
+        }
+        let mut tangents = HashMap::with_capacity(attributes.len());
+        for vertex in self.vertices() {
+            // Group the corners about the vertex that share a normal, texture
+            // coordinates, and handedness. Corners with degenerate texture
+            // coordinates have no handedness and join any group with the same
+            // normal and texture coordinates.
+            let zero = Scalar::<VertexPosition<G>>::zero();
+            let mut groups = Vec::<(_, _, TangentAccumulator<_>, Vec<_>)>::new();
+            for arc in vertex.outgoing_arcs() {
+                let face = if let Some(face) = arc.face() {
+                    face.key()
+                }
+                else {
+                    continue;
+                };
+                let next = arc.destination_vertex();
+                let previous = arc.previous_arc().source_vertex();
+                let (normal, uv) = attributes[&(face, vertex.key())];
+                let positions = [*vertex.position(), *next.position(), *previous.position()];
+                let uvs = [
+                    uv,
+                    attributes[&(face, next.key())].1,
+                    attributes[&(face, previous.key())].1,
+                ];
+                let sign = TangentAccumulator::handedness(normal, positions, uvs);
+                let index = if let Some(index) =
+                    groups.iter().position(|(attributes, handedness, ..)| {
+                        *attributes == (normal, uv)
+                            && (*handedness == sign || *handedness == zero || sign == zero)
+                    }) {
+                    index
+                }
+                else {
+                    groups.push((
+                        (normal, uv),
+                        sign,
+                        TangentAccumulator::new(normal),
+                        Vec::new(),
+                    ));
+                    groups.len() - 1
+                };
+                let (_, handedness, accumulator, faces) = &mut groups[index];
+                if *handedness == zero {
+                    *handedness = sign;
+                }
+                accumulator.insert(positions, uvs);
+                faces.push(face);
+            }
+            for (_, _, accumulator, faces) in groups {
+                let tangent = accumulator.into_tangent().ok_or(GraphError::Geometry)?;
+                tangents.extend(
+                    faces
+                        .into_iter()
+                        .map(|face| ((face, vertex.key()), tangent)),
+                );
+            }
+        }
+        Ok(tangents)
+    }
+
+    /// Computes a tangent for each vertex in the graph.
+    ///
+    /// The given function returns the normal and texture coordinates of a
+    /// vertex. See [`corner_tangents_with`]. Unlike [`corner_tangents_with`],
+    /// corners with mirrored texture coordinates cannot be split, so faces
+    /// with mirrored texture coordinates should not share vertices. The output
+    /// is well suited to [`to_mesh_by_vertex_with`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a tangent cannot be computed, such as when a normal
+    /// is zero or the faces about a vertex are degenerate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::{Point3, Vector3};
+    /// use plexus::buffer::MeshBuffer3;
+    /// use plexus::geometry::Tangent;
+    /// use plexus::graph::{MeshGraph, NormalWeight};
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::generate::Position;
+    /// use plexus::primitive::sphere::UvSphere;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// let mut graph: MeshGraph<E3> = UvSphere::new(16, 8).polygons::<Position<E3>>().collect();
+    /// graph.triangulate();
+    ///
+    /// let normals = graph.vertex_normals(NormalWeight::Angle).unwrap();
+    /// let tangents = graph
+    ///     .vertex_tangents_with(|vertex| {
+    ///         let position = vertex.position();
+    ///         (normals[&vertex.key()], (position.x, position.y))
+    ///     })
+    ///     .unwrap();
+    /// let buffer: MeshBuffer3<usize, (E3, Vector3<R64>, Vector3<R64>, R64)> = graph
+    ///     .to_mesh_by_vertex_with(|vertex| {
+    ///         let Tangent { vector, sign } = tangents[&vertex.key()];
+    ///         (*vertex.position(), normals[&vertex.key()], vector, sign)
+    ///     })
+    ///     .unwrap();
+    /// ```
+    ///
+    /// [`corner_tangents_with`]: crate::graph::MeshGraph::corner_tangents_with
+    /// [`to_mesh_by_vertex_with`]: crate::graph::MeshGraph::to_mesh_by_vertex_with
+    pub fn vertex_tangents_with<F>(
+        &self,
+        mut f: F,
+    ) -> Result<HashMap<VertexKey, Tangent<Vector<VertexPosition<G>>>>, GraphError>
+    where
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+        Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>> + PartialEq,
+        F: FnMut(
+            VertexView<&Self>,
+        ) -> (
+            Vector<VertexPosition<G>>,
+            (Scalar<VertexPosition<G>>, Scalar<VertexPosition<G>>),
+        ),
+    {
+        let attributes = self
+            .vertices()
+            .map(|vertex| (vertex.key(), f(vertex)))
+            .collect::<HashMap<_, _>>();
+        let mut tangents = HashMap::with_capacity(attributes.len());
+        for vertex in self.vertices() {
+            // All corners about the vertex share its normal and texture
+            // coordinates, so they are accumulated into a single tangent.
+            let (normal, uv) = attributes[&vertex.key()];
+            let mut accumulator = TangentAccumulator::new(normal);
+            let mut is_empty = true;
+            for arc in vertex.outgoing_arcs() {
+                if arc.face().is_none() {
+                    continue;
+                }
+                let next = arc.destination_vertex();
+                let previous = arc.previous_arc().source_vertex();
+                accumulator.insert(
+                    [*vertex.position(), *next.position(), *previous.position()],
+                    [uv, attributes[&next.key()].1, attributes[&previous.key()].1],
+                );
+                is_empty = false;
+            }
+            if !is_empty {
+                let tangent = accumulator.into_tangent().ok_or(GraphError::Geometry)?;
+                tangents.insert(vertex.key(), tangent);
+            }
+        }
+        Ok(tangents)
+    }
+
+    /// Smooths the positions of vertices in the graph.
+    ///
+    /// Each position is translated by its offset from its centroid scaled by
+    /// the given factor. The centroid of a vertex position is the mean of the
+    /// positions of its adjacent vertices. That is, given a factor $k$ and a
+    /// vertex with position $P$ and centroid $Q$, its position becomes
+    /// $P+k(Q-P)$.
+    pub fn smooth<T>(&mut self, factor: T)
+    where
+        T: Into<Scalar<VertexPosition<G>>>,
+        G: VertexCentroid,
+        G::Vertex: AsPositionMut,
+        VertexPosition<G>: EuclideanSpace,
+    {
+        let factor = factor.into();
+        let mut positions = HashMap::with_capacity(self.vertex_count());
+        for vertex in self.vertices() {
+            let position = *vertex.position();
+            positions.insert(
+                vertex.key(),
+                position + ((vertex.centroid() - position) * factor),
+            );
+        }
+        for mut vertex in self.vertex_orphans() {
+            *vertex.get_mut().as_position_mut() = positions.remove(&vertex.key()).unwrap();
+        }
+    }
+
+    /// Planarizes the faces of the graph.
+    ///
+    /// See [`planarize_with`].
+    ///
+    /// [`planarize_with`]: crate::graph::MeshGraph::planarize_with
+    pub fn planarize<T>(
+        &mut self,
+        tolerance: T,
+        limit: usize,
+    ) -> Result<Scalar<VertexPosition<G>>, GraphError>
+    where
+        T: Into<Scalar<VertexPosition<G>>>,
+        G: FacePlane,
+        G::Vertex: AsPositionMut,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    {
+        self.planarize_with(tolerance, limit, |_| false)
+    }
+
+    /// Planarizes the faces of the graph, keeping vertices for which the given
+    /// predicate returns `true` fixed.
+    ///
+    /// Unlike [`FaceView::flatten`], which moves the vertices of a single face
+    /// and may break the planarity of adjacent faces, this function
+    /// iteratively moves all vertices that are shared by faces. In each
+    /// iteration, every vertex is projected onto the best-fit plane of each of
+    /// its adjacent faces and is moved to the mean of these projections. This
+    /// is a least squares compromise between the faces and moves vertices as
+    /// little as possible. Triangles are always planar and do not constrain
+    /// their vertices.
+    ///
+    /// Iteration stops when the distance from every vertex to the best-fit
+    /// planes of its faces is less than or equal to the given tolerance or
+    /// after the given number of iterations. Returns the greatest such
+    /// distance, which may exceed the tolerance if the iteration limit is
+    /// reached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a best-fit plane could not be computed for a face.
+    ///
+    /// # Examples
+    ///
+    /// Planarizing a graph while keeping its boundary fixed:
+    ///
+    /// ```rust
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use nalgebra::Point3;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::Tetragon;
+    ///
+    /// let mut graph = MeshGraph::<Point3<f64>>::from_raw_buffers(
+    ///     vec![
+    ///         Tetragon::new(0usize, 1, 4, 3),
+    ///         Tetragon::new(1, 2, 5, 4),
+    ///         Tetragon::new(3, 4, 7, 6),
+    ///         Tetragon::new(4, 5, 8, 7),
+    ///     ],
+    ///     (0..9)
+    ///         .map(|index| {
+    ///             let z = if index == 4 { 0.5 } else { 0.0 };
+    ///             ((index % 3) as f64, (index / 3) as f64, z)
+    ///         })
+    ///         .collect::<Vec<_>>(),
+    /// )
+    /// .unwrap();
+    /// let deviation = graph
+    ///     .planarize_with(1e-6, 100, |vertex| {
+    ///         vertex
+    ///             .outgoing_arcs()
+    ///             .any(|arc| arc.is_boundary_arc() || arc.opposite_arc().is_boundary_arc())
+    ///     })
+    ///     .unwrap();
+    /// assert!(deviation <= 1e-6);
+    /// ```
+    ///
+    /// [`FaceView::flatten`]: crate::graph::FaceView::flatten
+    pub fn planarize_with<T, F>(
+        &mut self,
+        tolerance: T,
+        limit: usize,
+        mut f: F,
+    ) -> Result<Scalar<VertexPosition<G>>, GraphError>
+    where
+        T: Into<Scalar<VertexPosition<G>>>,
+        G: FacePlane,
+        G::Vertex: AsPositionMut,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+        F: FnMut(VertexView<&Self>) -> bool,
+    {
+        let tolerance = tolerance.into();
+        let zero = Scalar::<VertexPosition<G>>::zero();
+        let one = Scalar::<VertexPosition<G>>::one();
+        let fixed = self
+            .vertices()
+            .filter(|vertex| f(*vertex))
+            .map(|vertex| vertex.key())
+            .collect::<HashSet<_>>();
+        let mut iteration = 0;
+        loop {
+            let mut deviation = zero;
+            let mut offsets = HashMap::with_capacity(self.vertex_count());
+            for face in self.faces().filter(|face| face.arity() > 3) {
+                let plane = face.plane()?;
+                let normal = *plane.normal.get();
+                for vertex in face.adjacent_vertices() {
+                    let distance = (*vertex.position() - plane.origin).dot(normal);
+                    let magnitude = if distance < zero { -distance } else { distance };
+                    if magnitude > deviation {
+                        deviation = magnitude;
+                    }
+                    let (offset, count) = offsets
+                        .entry(vertex.key())
+                        .or_insert((Vector::<VertexPosition<G>>::zero(), zero));
+                    *offset = *offset - (normal * distance);
+                    *count = *count + one;
+                }
+            }
+            if deviation <= tolerance || iteration >= limit {
+                return Ok(deviation);
+            }
+            for mut vertex in self.vertex_orphans() {
+                if fixed.contains(&vertex.key()) {
+                    continue;
+                }
+                if let Some((offset, count)) = offsets.remove(&vertex.key()) {
+                    let position = vertex.get_mut().as_position_mut();
+                    *position = *position + (offset * (one / count));
+                }
+            }
+            iteration += 1;
+        }
+    }
+
+    /// Splits the graph along a path.
+    ///
+    /// Splitting a graph creates boundaries along the given path and copies any
+    /// necessary vertex, arc, and edge data.
+    ///
+    /// If the path bisects the graph, then splitting will result in disjointed
+    /// sub-graphs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use nalgebra::Point2;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::Trigon;
+    ///
+    /// type E2 = Point2<f64>;
+    ///
+    /// // Create a graph from two triangles.
+    /// let mut graph = MeshGraph::<E2>::from_raw_buffers(
+    ///     vec![Trigon::new(0usize, 1, 2), Trigon::new(2, 1, 3)],
+    ///     vec![
+    ///         (-1.0, 0.0),
+    ///         (0.0, -1.0),
+    ///         (0.0, 1.0),
+    ///         (1.0, 0.0),
+    ///     ],
+    /// )
+    /// .unwrap();
+    ///
+    /// // Find the shared edge that bisects the triangles and then construct a path
+    /// // along the edge and split the graph.
+    /// let key = graph
+    ///     .edges()
+    ///     .find(|edge| !edge.is_boundary_edge())
+    ///     .map(|edge| edge.into_arc().key())
+    ///     .unwrap();
+    /// let mut path = graph.arc_mut(key).unwrap().into_path();
+    /// MeshGraph::split_at_path(path).unwrap();
+    /// ```
+    pub fn split_at_path(path: Path<&mut Self>) -> Result<(), GraphError> {
+        let _ = path;
+        unimplemented!()
+    }
+
+    /// Gets an iterator over a vertex within each disjoint sub-graph.
+    ///
+    /// Traverses the graph and returns an arbitrary vertex within each
+    /// _disjoint sub-graph_. A sub-graph is _disjoint_ if it cannot be reached
+    /// from all other topology in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use nalgebra::Point2;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::Trigon;
+    ///
+    /// type E2 = Point2<f64>;
+    ///
+    /// // Create a graph from two disjoint triangles.
+    /// let graph = MeshGraph::<E2>::from_raw_buffers(
+    ///     vec![Trigon::new(0u32, 1, 2), Trigon::new(3, 4, 5)],
+    ///     vec![
+    ///         (-2.0, 0.0),
+    ///         (-1.0, 0.0),
+    ///         (-1.0, 1.0),
+    ///         (1.0, 0.0),
+    ///         (2.0, 0.0),
+    ///         (1.0, 1.0),
+    ///     ],
+    /// )
+    /// .unwrap();
+    ///
+    /// // A vertex from each disjoint triangle is returned.
+    /// for vertex in graph.disjoint_subgraph_vertices() {
+    ///     // ...
+    /// }
+    /// ```
+    pub fn disjoint_subgraph_vertices(&self) -> impl ExactSizeIterator<Item = VertexView<&Self>> {
+        let keys = self
+            .core
+            .vertices
+            .iter()
+            .map(|(key, _)| key)
+            .collect::<HashSet<_>>();
+        let mut subkeys = HashSet::with_capacity(self.vertex_count());
+        let mut vertices = SmallVec::<[VertexView<_>; 4]>::new();
+        while let Some(key) = keys.difference(&subkeys).nth(0) {
+            let vertex = VertexView::from(View::bind_unchecked(self, *key));
+            vertices.push(vertex);
+            subkeys.extend(vertex.traverse_by_depth().map(|vertex| vertex.key()));
+        }
+        vertices.into_iter()
+    }
+
+    /// Orients the faces of each disjoint sub-graph.
+    ///
+    /// Non-orientable sub-graphs can only be detected given the
+    /// [`RepairReport`] produced when the graph was constructed. Without it,
+    /// no sub-graph is reported as non-orientable.
+    ///
+    /// Because arcs are directed and belong to at most one face, the faces of
+    /// a graph are always wound consistently. Input with inconsistent winding
+    /// is flipped by [`from_raw_buffers_with_repair`], which propagates a
+    /// consistent orientation across the faces of each connected component by
+    /// breadth. Non-orientable input, such as a Möbius strip, is instead split
+    /// along edges at which its winding conflicts, after which it is no longer
+    /// distinguishable from orientable input. Sub-graphs formed from such
+    /// input are reported as non-orientable and are not reoriented. Keys in
+    /// the report refer to the graph as it was constructed, so sub-graphs that
+    /// have since been removed are ignored.
+    ///
+    /// The orientation of each closed sub-graph is determined by the sign of
+    /// its volume (see [`volume`]). If the sign disagrees with the given
+    /// [`Orientation`], then the winding of all of its faces is reversed.
+    /// Sub-graphs with boundaries (that are not closed) have no volume and are
+    /// not reoriented; such sub-graphs are reported as open.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::Point3;
+    /// use plexus::graph::{MeshGraph, Orientation};
+    /// use plexus::index::HashIndexer;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::cube::Cube;
+    /// use plexus::primitive::generate::Position;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// let mut graph: MeshGraph<E3> = Cube::new()
+    ///     .polygons::<Position<E3>>()
+    ///     .collect_with_indexer(HashIndexer::default())
+    ///     .unwrap();
+    /// let report = graph.orient(Orientation::Outward, None);
+    /// assert!(report.reversed().is_empty());
+    /// ```
+    ///
+    /// [`from_raw_buffers_with_repair`]: crate::graph::MeshGraph::from_raw_buffers_with_repair
+    /// [`volume`]: crate::graph::MeshGraph::volume
+    /// [`Orientation`]: crate::graph::Orientation
+    /// [`RepairReport`]: crate::graph::RepairReport
+    pub fn orient(
+        &mut self,
+        orientation: Orientation,
+        repair: Option<&RepairReport>,
+    ) -> OrientationReport
+    where
+        G: FaceCentroid,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    {
+        let non_orientable = repair
+            .map(|repair| repair.non_orientable_keys.iter().cloned().collect())
+            .unwrap_or_else(HashSet::new);
+        let mut report = OrientationReport::default();
+        for keys in self.disjoint_subgraph_faces() {
+            let key = keys[0];
+            report.components.push(key);
+            if keys.iter().any(|key| non_orientable.contains(key)) {
+                report.non_orientable.push(key);
+                continue;
+            }
+            let is_closed = keys.iter().all(|key| {
+                self.face(*key)
+                    .expect_consistent()
+                    .adjacent_arcs()
+                    .all(|arc| !arc.opposite_arc().is_boundary_arc())
+            });
+            if !is_closed {
+                report.open.push(key);
+                continue;
+            }
+            let volume =
+                integral::moments(keys.iter().map(|key| self.face(*key).expect_consistent()))
+                    .volume;
+            let is_reversed = match orientation {
+                Orientation::Inward => volume > Zero::zero(),
+                Orientation::Outward => volume < Zero::zero(),
+            };
+            if is_reversed {
+                let cache = FaceReverseCache::from_component(self.face(key).expect_consistent())
+                    .expect_consistent();
+                Mutation::take(self)
+                    .bypass_or_commit_with(|mutation| mutation::face::reverse(mutation, cache))
+                    .map_err(|(_, error)| error)
+                    .expect_consistent();
+                report.reversed.push(key);
+            }
+        }
+        report
+    }
+
+    // Gets the keys of the faces in each disjoint sub-graph. Each collection of
+    // keys is non-empty.
+    fn disjoint_subgraph_faces(&self) -> Vec<Vec<FaceKey>> {
+        let mut visited = HashSet::with_capacity(self.face_count());
+        let mut subgraphs = Vec::new();
+        for face in self.faces() {
+            if visited.contains(&face.key()) {
+                continue;
+            }
+            let keys = face
+                .traverse_by_breadth()
+                .map(|face| face.key())
+                .collect::<Vec<_>>();
+            visited.extend(keys.iter().cloned());
+            subgraphs.push(keys);
+        }
+        subgraphs
+    }
+
+    /// Moves disjoint sub-graphs into separate graphs.
+    pub fn into_disjoint_subgraphs(self) -> Vec<Self> {
+        unimplemented!()
+    }
+
+    /// Creates a [`MeshGraph`] from [raw buffers][`buffer`], repairing
+    /// non-manifold topology.
+    ///
+    /// Unlike [`from_raw_buffers`], this function tolerates input that cannot
+    /// be represented by a graph and rewrites it into a manifold form:
+    ///
+    /// - Faces are flipped to agree with the winding of adjacent faces.
+    /// - Edges shared by more than two faces are split.
+    /// - Vertices that pinch together more than one fan of faces are
+    ///   duplicated.
+    /// - Degenerate faces are discarded.
+    ///
+    /// Duplicated vertices are cloned from the input vertex buffer. Vertices
+    /// are never discarded. The changes are described by the returned
+    /// [`RepairReport`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any index is out of bounds or there is an error
+    /// inserting topology into the graph. As with [`from_raw_buffers`], every
+    /// vertex must be referenced by a face that is not discarded.
+    ///
+    /// # Examples
+    ///
+    /// Creating a graph from two triangles that share only a vertex:
+    ///
+    /// ```rust
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use nalgebra::Point2;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::primitive::Trigon;
+    ///
+    /// let (graph, report) = MeshGraph::<Point2<f64>>::from_raw_buffers_with_repair(
+    ///     vec![Trigon::new(0usize, 1, 2), Trigon::new(0, 3, 4)],
+    ///     vec![
+    ///         (0.0, 0.0),
+    ///         (1.0, 0.0),
+    ///         (1.0, 1.0),
+    ///         (-1.0, 0.0),
+    ///         (-1.0, -1.0),
+    ///     ],
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(&[0], report.duplicated_vertices());
+    /// assert_eq!(6, graph.vertex_count());
+    /// ```
+    ///
+    /// [`buffer`]: crate::buffer
+    /// [`from_raw_buffers`]: crate::buffer::FromRawBuffers::from_raw_buffers
+    /// [`MeshGraph`]: crate::graph::MeshGraph
+    /// [`RepairReport`]: crate::graph::RepairReport
+    pub fn from_raw_buffers_with_repair<P, H, I, J>(
+        indices: I,
+        vertices: J,
+    ) -> Result<(Self, RepairReport), GraphError>
+    where
+        P: IntoVertices + Polygonal,
+        P::Vertex: Integer + ToPrimitive + Unsigned,
+        G::Vertex: FromGeometry<H>,
+        I: IntoIterator<Item = P>,
+        J: IntoIterator<Item = H>,
+    {
+        let vertices = vertices
+            .into_iter()
+            .map(|vertex| vertex.into_geometry())
+            .collect::<Vec<G::Vertex>>();
+        let faces = indices
+            .into_iter()
+            .map(|face| {
+                face.into_vertices()
+                    .into_iter()
+                    .map(|index| {
+                        <usize as NumCast>::from(index)
+                            .filter(|index| *index < vertices.len())
+                            .ok_or(GraphError::TopologyNotFound)
+                    })
+                    .collect::<Result<SmallVec<_>, _>>()
+                    .map(|perimeter| (perimeter, G::Face::default()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        MeshGraph::from_repaired_buffers(faces, vertices)
+    }
+
+    /// Creates a [`MeshGraph`] from an encoding, repairing non-manifold
+    /// topology.
+    ///
+    /// See [`from_raw_buffers_with_repair`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any index is out of bounds or there is an error
+    /// inserting topology into the graph.
+    ///
+    /// [`from_raw_buffers_with_repair`]: crate::graph::MeshGraph::from_raw_buffers_with_repair
+    /// [`MeshGraph`]: crate::graph::MeshGraph
+    pub fn from_encoding_with_repair<E>(
+        vertices: <E as VertexDecoder>::Output,
+        faces: <E as FaceDecoder>::Output,
+    ) -> Result<(Self, RepairReport), GraphError>
+    where
+        E: FaceDecoder + VertexDecoder,
+        G::Face: FromGeometry<E::Face>,
+        G::Vertex: FromGeometry<E::Vertex>,
+    {
+        let vertices = vertices
+            .into_iter()
+            .map(|vertex| vertex.into_geometry())
+            .collect::<Vec<G::Vertex>>();
+        let faces = faces
+            .into_iter()
+            .map(|(perimeter, data)| {
+                perimeter
+                    .into_iter()
+                    .map(|index| {
+                        if index < vertices.len() {
+                            Ok(index)
+                        }
+                        else {
+                            Err(GraphError::TopologyNotFound)
+                        }
+                    })
+                    .collect::<Result<SmallVec<_>, _>>()
+                    .map(|perimeter| (perimeter, data.into_geometry()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        MeshGraph::from_repaired_buffers(faces, vertices)
+    }
+
+    /// Creates a [`MeshGraph`] from an iterator of $n$-gons using the given
+    /// indexer, repairing non-manifold topology.
+    ///
+    /// This is the repairing counterpart of [`FromIndexer`] and
+    /// [`collect_with_indexer`]. Vertices are indexed as usual and the
+    /// resulting buffers are repaired as described by
+    /// [`from_raw_buffers_with_repair`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is an error inserting topology into the
+    /// graph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use nalgebra::Point2;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::index::HashIndexer;
+    /// use plexus::primitive::Trigon;
+    ///
+    /// // Two triangles that share only a vertex.
+    /// let (graph, report) = MeshGraph::<Point2<i32>>::from_indexer_with_repair(
+    ///     vec![
+    ///         Trigon::new((0, 0), (1, 0), (1, 1)),
+    ///         Trigon::new((0, 0), (-1, 0), (-1, -1)),
+    ///     ],
+    ///     HashIndexer::default(),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(1, report.duplicated_vertices().len());
+    /// assert_eq!(6, graph.vertex_count());
+    /// ```
+    ///
+    /// [`collect_with_indexer`]: crate::index::CollectWithIndexer::collect_with_indexer
+    /// [`from_raw_buffers_with_repair`]: crate::graph::MeshGraph::from_raw_buffers_with_repair
+    /// [`FromIndexer`]: crate::index::FromIndexer
+    /// [`MeshGraph`]: crate::graph::MeshGraph
+    pub fn from_indexer_with_repair<P, I, N>(
+        input: I,
+        indexer: N,
+    ) -> Result<(Self, RepairReport), GraphError>
     where
-        T: Into<Scalar<VertexPosition<G>>>,
-        G: VertexCentroid,
-        G::Vertex: AsPositionMut,
-        VertexPosition<G>: EuclideanSpace,
+        G::Vertex: FromGeometry<P::Vertex>,
+        P: Map<usize> + Polygonal,
+        P::Output: Grouping<Group = P::Output> + IntoVertices + Polygonal<Vertex = usize>,
+        Vec<P::Output>: IndexBuffer<P::Output, Index = usize>,
+        I: IntoIterator<Item = P>,
+        N: Indexer<P, P::Vertex>,
     {
-        let factor = factor.into();
-        let mut positions = HashMap::with_capacity(self.vertex_count());
-        for vertex in self.vertices() {
-            let position = *vertex.position();
-            positions.insert(
-                vertex.key(),
-                position + ((vertex.centroid() - position) * factor),
-            );
-        }
-        for mut vertex in self.vertex_orphans() {
-            *vertex.get_mut().as_position_mut() = positions.remove(&vertex.key()).unwrap();
+        let (indices, vertices) = input.into_iter().index_vertices(indexer);
+        let vertices = vertices
+            .into_iter()
+            .map(|vertex| vertex.into_geometry())
+            .collect::<Vec<G::Vertex>>();
+        let faces = indices
+            .into_iter()
+            .map(|face| {
+                let perimeter = face.into_vertices().into_iter().collect::<SmallVec<_>>();
+                (perimeter, G::Face::default())
+            })
+            .collect::<Vec<_>>();
+        MeshGraph::from_repaired_buffers(faces, vertices)
+    }
+
+    fn from_repaired_buffers(
+        faces: Vec<(SmallVec<[usize; 4]>, G::Face)>,
+        vertices: Vec<G::Vertex>,
+    ) -> Result<(Self, RepairReport), GraphError> {
+        let (perimeters, mut data): (Vec<_>, Vec<_>) = faces.into_iter().unzip();
+        let repair::Repair {
+            faces: perimeters,
+            origins,
+            vertices: sources,
+            non_orientable,
+            mut report,
+        } = repair::repair(perimeters, vertices.len());
+        let mut mutation = Mutation::from(MeshGraph::new());
+        let keys = sources
+            .into_iter()
+            .map(|source| mutation::vertex::insert(&mut mutation, vertices[source].clone()))
+            .collect::<Vec<_>>();
+        for ((perimeter, origin), is_non_orientable) in
+            perimeters.into_iter().zip(origins).zip(non_orientable)
+        {
+            let perimeter = perimeter
+                .into_iter()
+                .map(|index| keys[index])
+                .collect::<SmallVec<[_; 4]>>();
+            let cache = FaceInsertCache::from_storage(&mutation, &perimeter)?;
+            let data = mem::take(&mut data[origin]);
+            let key =
+                mutation::face::insert_with(&mut mutation, cache, || (Default::default(), data))?;
+            if is_non_orientable {
+                report.non_orientable_keys.push(key);
+            }
         }
+        mutation
+            .commit()
+            .map(|graph| (graph, report))
+            .map_err(|(_, error)| error)
     }
 
-    /// Splits the graph along a path.
+    /// Validates the consistency of the graph.
     ///
-    /// Splitting a graph creates boundaries along the given path and copies any
-    /// necessary vertex, arc, and edge data.
+    /// Inspects the graph for violations of the invariants of its half-edge
+    /// representation, such as asymmetric opposite arcs, inconsistent next and
+    /// previous arcs, malformed face rings, missing leading arcs, dangling
+    /// edges, non-manifold vertices, and degenerate faces. Each violation is
+    /// reported as a [`Defect`] that names the key of the offending entity.
     ///
-    /// If the path bisects the graph, then splitting will result in disjointed
-    /// sub-graphs.
+    /// Graphs are consistent by construction and the mutation API maintains
+    /// this consistency, so this function is primarily useful for diagnosing
+    /// errors (and panics) that occur after complex mutations.
     ///
     /// # Examples
     ///
-    /// ```rust,no_run
+    /// ```rust
+    /// # extern crate decorum;
     /// # extern crate nalgebra;
     /// # extern crate plexus;
     /// #
-    /// use nalgebra::Point2;
+    /// use decorum::R64;
+    /// use nalgebra::Point3;
     /// use plexus::graph::MeshGraph;
+    /// use plexus::index::HashIndexer;
     /// use plexus::prelude::*;
-    /// use plexus::primitive::Trigon;
-    ///
-    /// type E2 = Point2<f64>;
+    /// use plexus::primitive::cube::Cube;
+    /// use plexus::primitive::generate::Position;
     ///
-    /// // Create a graph from two triangles.
-    /// let mut graph = MeshGraph::<E2>::from_raw_buffers(
-    ///     vec![Trigon::new(0usize, 1, 2), Trigon::new(2, 1, 3)],
-    ///     vec![
-    ///         (-1.0, 0.0),
-    ///         (0.0, -1.0),
-    ///         (0.0, 1.0),
-    ///         (1.0, 0.0),
-    ///     ],
-    /// )
-    /// .unwrap();
+    /// type E3 = Point3<R64>;
     ///
-    /// // Find the shared edge that bisects the triangles and then construct a path
-    /// // along the edge and split the graph.
-    /// let key = graph
-    ///     .edges()
-    ///     .find(|edge| !edge.is_boundary_edge())
-    ///     .map(|edge| edge.into_arc().key())
+    /// let mut graph: MeshGraph<E3> = Cube::new()
+    ///     .polygons::<Position<E3>>()
+    ///     .collect_with_indexer(HashIndexer::default())
     ///     .unwrap();
-    /// let mut path = graph.arc_mut(key).unwrap().into_path();
-    /// MeshGraph::split_at_path(path).unwrap();
+    /// let key = graph.faces().nth(0).unwrap().key();
+    /// graph
+    ///     .face_mut(key)
+    ///     .unwrap()
+    ///     .extrude_with_offset(1.0)
+    ///     .unwrap();
+    ///
+    /// let report = graph.validate();
+    /// assert!(report.is_valid());
     /// ```
-    pub fn split_at_path(path: Path<&mut Self>) -> Result<(), GraphError> {
-        let _ = path;
-        unimplemented!()
+    ///
+    /// [`Defect`]: crate::graph::Defect
+    pub fn validate(&self) -> ValidationReport {
+        validation::validate(self)
     }
 
-    /// Gets an iterator over a vertex within each disjoint sub-graph.
+    /// Computes the topological invariants of the graph.
     ///
-    /// Traverses the graph and returns an arbitrary vertex within each
-    /// _disjoint sub-graph_. A sub-graph is _disjoint_ if it cannot be reached
-    /// from all other topology in the graph.
+    /// The Euler characteristic, boundary loops, and genus are summed over
+    /// the disjoint sub-graphs of the graph. These invariants are useful for
+    /// verifying that an operation preserves the topology of a surface.
     ///
     /// # Examples
     ///
     /// ```rust
+    /// # extern crate decorum;
     /// # extern crate nalgebra;
     /// # extern crate plexus;
     /// #
-    /// use nalgebra::Point2;
+    /// use decorum::R64;
+    /// use nalgebra::Point3;
     /// use plexus::graph::MeshGraph;
     /// use plexus::prelude::*;
-    /// use plexus::primitive::Trigon;
+    /// use plexus::primitive::generate::Position;
+    /// use plexus::primitive::sphere::UvSphere;
     ///
-    /// type E2 = Point2<f64>;
+    /// type E3 = Point3<R64>;
     ///
-    /// // Create a graph from two disjoint triangles.
-    /// let graph = MeshGraph::<E2>::from_raw_buffers(
-    ///     vec![Trigon::new(0u32, 1, 2), Trigon::new(3, 4, 5)],
-    ///     vec![
-    ///         (-2.0, 0.0),
-    ///         (-1.0, 0.0),
-    ///         (-1.0, 1.0),
-    ///         (1.0, 0.0),
-    ///         (2.0, 0.0),
-    ///         (1.0, 1.0),
-    ///     ],
-    /// )
-    /// .unwrap();
+    /// let mut graph: MeshGraph<E3> = UvSphere::new(16, 8).polygons::<Position<E3>>().collect();
+    /// let key = graph.faces().nth(0).unwrap().key();
+    /// graph.face_mut(key).unwrap().poke_with_offset(0.5).unwrap();
     ///
-    /// // A vertex from each disjoint triangle is returned.
-    /// for vertex in graph.disjoint_subgraph_vertices() {
-    ///     // ...
-    /// }
+    /// let invariants = graph.invariants();
+    /// assert_eq!(2, invariants.euler_characteristic());
+    /// assert_eq!(Some(0), invariants.genus());
+    /// assert!(invariants.is_closed());
     /// ```
-    pub fn disjoint_subgraph_vertices(&self) -> impl ExactSizeIterator<Item = VertexView<&Self>> {
-        let keys = self
-            .core
-            .vertices
-            .iter()
-            .map(|(key, _)| key)
-            .collect::<HashSet<_>>();
-        let mut subkeys = HashSet::with_capacity(self.vertex_count());
-        let mut vertices = SmallVec::<[VertexView<_>; 4]>::new();
-        while let Some(key) = keys.difference(&subkeys).nth(0) {
-            let vertex = VertexView::from(View::bind_unchecked(self, *key));
-            vertices.push(vertex);
-            subkeys.extend(vertex.traverse_by_depth().map(|vertex| vertex.key()));
-        }
-        vertices.into_iter()
+    pub fn invariants(&self) -> Invariants {
+        self.disjoint_subgraph_invariants().into_iter().sum()
     }
 
-    /// Moves disjoint sub-graphs into separate graphs.
-    pub fn into_disjoint_subgraphs(self) -> Vec<Self> {
-        unimplemented!()
+    /// Computes the topological invariants of each disjoint sub-graph.
+    ///
+    /// The invariants are in the same order as the vertices returned by
+    /// [`disjoint_subgraph_vertices`].
+    ///
+    /// [`disjoint_subgraph_vertices`]: crate::graph::MeshGraph::disjoint_subgraph_vertices
+    pub fn disjoint_subgraph_invariants(&self) -> Vec<Invariants> {
+        self.disjoint_subgraph_vertices()
+            .map(|vertex| topology::invariants(self, vertex.key()))
+            .collect()
     }
 
     /// Shrinks the capacity of the graph's underlying storage as much as
@@ -1117,6 +3082,138 @@ where
         })?;
         builder.build()
     }
+
+    /// Creates a [`Buildable`] mesh data structure from the graph, splitting
+    /// vertices along creases.
+    ///
+    /// The corners of faces about each vertex are joined into a single output
+    /// vertex unless they are separated by a crease. An edge is a crease if
+    /// the angle between the normals of its faces (its dihedral angle) exceeds
+    /// the given angle in radians or if the given predicate returns `true`
+    /// for the edge (a hard edge). This produces the fewest vertices that
+    /// support both smooth and faceted shading. The normal of each output
+    /// vertex is the mean of the normals of the faces that it joins, weighted
+    /// in the same manner as [`VertexView::normal_with`], and is passed to the
+    /// given function along with the vertex. Face data is converted into the
+    /// `Facet` type of the output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the normal of a face or output vertex cannot be
+    /// computed, the vertex data cannot be inserted into the output, there are
+    /// arity conflicts, or the output does not support topology found in the
+    /// graph.
+    ///
+    /// # Examples
+    ///
+    /// Creating a [`MeshBuffer`] with faceted shading across the edges of a
+    /// cube:
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::{Point3, Vector3};
+    /// use plexus::buffer::MeshBuffer4;
+    /// use plexus::graph::{MeshGraph, NormalWeight};
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::cube::Cube;
+    /// use plexus::primitive::generate::Position;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// let graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+    /// let buffer: MeshBuffer4<usize, (E3, Vector3<R64>)> = graph
+    ///     .to_mesh_by_crease_with(
+    ///         0.5,
+    ///         NormalWeight::Angle,
+    ///         |_| false,
+    ///         |vertex, normal| (*vertex.position(), normal),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(24, buffer.as_vertex_slice().len());
+    /// ```
+    ///
+    /// [`Buildable`]: crate::builder::Buildable
+    /// [`MeshBuffer`]: crate::buffer::MeshBuffer
+    /// [`VertexView::normal_with`]: crate::graph::VertexView::normal_with
+    pub fn to_mesh_by_crease_with<B, T, P, F>(
+        &self,
+        angle: T,
+        weight: NormalWeight,
+        mut is_hard: P,
+        mut f: F,
+    ) -> Result<B, GraphError>
+    where
+        B: Buildable,
+        B::Error: Into<GraphError>,
+        B::Facet: FromGeometry<G::Face>,
+        T: Into<Scalar<VertexPosition<G>>>,
+        G: FaceArea + FaceNormal,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+        P: FnMut(EdgeView<&Self>) -> bool,
+        F: FnMut(VertexView<&Self>, Vector<VertexPosition<G>>) -> B::Vertex,
+    {
+        let normals = self
+            .faces()
+            .map(|face| face.normal().map(|normal| (face.key(), normal)))
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        let cos = Real::cos(angle.into());
+        let wedges = atlas::wedges(self, |edge| {
+            if is_hard(edge) {
+                return true;
+            }
+            let arc = edge.into_arc();
+            match (arc.face(), arc.opposite_arc().face()) {
+                (Some(a), Some(b)) => normals[&a.key()].dot(normals[&b.key()]) < cos,
+                _ => false,
+            }
+        });
+        let mut wedge_normals = HashMap::new();
+        for (key, wedge) in wedges.iter() {
+            let arc = self.arc(*key).expect_consistent();
+            let face = arc.face().expect_consistent();
+            let scale = geometry::corner_weight(arc, weight)?;
+            let normal = wedge_normals
+                .entry(*wedge)
+                .or_insert_with(Vector::<VertexPosition<G>>::zero);
+            *normal = *normal + (normals[&face.key()] * scale);
+        }
+        let wedge_normals = wedge_normals
+            .into_iter()
+            .map(|(wedge, normal)| {
+                normal
+                    .normalize()
+                    .map(|normal| (wedge, normal))
+                    .ok_or(GraphError::Geometry)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut builder = B::builder();
+        builder
+            .surface_with(|builder| {
+                let mut keys = HashMap::with_capacity(wedge_normals.len());
+                for (wedge, normal) in wedge_normals {
+                    let vertex = self.arc(wedge).expect_consistent().into_source_vertex();
+                    keys.insert(wedge, builder.insert_vertex(f(vertex, normal))?);
+                }
+                builder.facets_with(|builder| {
+                    for face in self.faces() {
+                        let indices = face
+                            .adjacent_arcs()
+                            .map(|arc| keys[&wedges[&arc.key()]])
+                            .collect::<SmallVec<[_; 8]>>();
+                        builder.insert_facet(indices.as_slice(), face.get().clone())?;
+                    }
+                    Ok(())
+                })
+            })
+            .map_err(|error| error.into())?;
+        builder.build().map_err(|error| error.into())
+    }
 }
 
 impl<G> AsStorage<Vertex<G>> for MeshGraph<G>
@@ -1632,12 +3729,16 @@ mod tests {
     use nalgebra::{Point2, Point3, Vector3};
     use num::Zero;
 
-    use crate::buffer::MeshBuffer3;
-    use crate::graph::{GraphData, GraphError, MeshGraph};
+    use crate::buffer::{MeshBuffer3, MeshBuffer4};
+    use crate::graph::{
+        fixture, FaceView, GraphData, GraphError, MeshGraph, NormalWeight, Orientation,
+    };
+    use crate::index::HashIndexer;
     use crate::prelude::*;
+    use crate::primitive::cube::Cube;
     use crate::primitive::generate::Position;
     use crate::primitive::sphere::UvSphere;
-    use crate::primitive::NGon;
+    use crate::primitive::{NGon, Tetragon, Trigon};
 
     type E2 = Point2<R64>;
     type E3 = Point3<R64>;
@@ -1727,6 +3828,271 @@ mod tests {
         assert_eq!(graph.err().unwrap(), GraphError::TopologyConflict);
     }
 
+    #[test]
+    fn orient_outward() {
+        // Construct a cube with inward facing normals.
+        let (indices, vertices) = Cube::new()
+            .polygons::<Position<E3>>()
+            .index_vertices::<Tetragon<usize>, _>(HashIndexer::default());
+        let indices = indices
+            .into_iter()
+            .map(|NGon([a, b, c, d])| NGon([d, c, b, a]))
+            .collect::<Vec<_>>();
+        let mut graph = MeshGraph::<E3>::from_raw_buffers(indices, vertices).unwrap();
+
+        let report = graph.orient(Orientation::Outward, None);
+        assert_eq!(1, report.components().len());
+        assert_eq!(1, report.reversed().len());
+        assert!(graph.validate().is_valid());
+
+        // The graph is already oriented outward, so it should not be reversed
+        // again.
+        let report = graph.orient(Orientation::Outward, None);
+        assert!(report.reversed().is_empty());
+
+        // Orienting the graph inward reverses it once more.
+        let report = graph.orient(Orientation::Inward, None);
+        assert_eq!(1, report.reversed().len());
+        assert!(graph.validate().is_valid());
+    }
+
+    #[test]
+    fn orient_non_orientable() {
+        // A triangulated Möbius strip. Its winding conflicts along an edge,
+        // which is split when the graph is constructed.
+        let (mut graph, repair) = fixture::mobius();
+        assert_eq!(1, repair.non_orientable_faces().len());
+
+        let report = graph.orient(Orientation::Outward, Some(&repair));
+        assert!(!report.non_orientable().is_empty());
+        assert_eq!(report.components().len(), report.non_orientable().len());
+        assert!(report.reversed().is_empty());
+    }
+
+    #[test]
+    fn orient_coincident_vertices() {
+        // Two triangles with coincident but distinct vertices that are wound in
+        // opposite directions. These are disjoint and orientable.
+        let (mut graph, repair) = MeshGraph::<Point3<f64>>::from_raw_buffers_with_repair(
+            vec![Trigon::new(0usize, 1, 2), Trigon::new(5, 4, 3)],
+            vec![
+                (0.0, 0.0, 0.0),
+                (1.0, 0.0, 0.0),
+                (0.0, 1.0, 0.0),
+                (0.0, 0.0, 0.0),
+                (1.0, 0.0, 0.0),
+                (0.0, 1.0, 0.0),
+            ],
+        )
+        .unwrap();
+        assert!(repair.is_unchanged());
+
+        let report = graph.orient(Orientation::Outward, Some(&repair));
+        assert_eq!(2, report.components().len());
+        assert!(report.non_orientable().is_empty());
+        assert_eq!(2, report.open().len());
+    }
+
+    #[test]
+    fn mass_properties() {
+        let mut graph: MeshGraph<E3> = Cube::new()
+            .polygons::<Position<E3>>()
+            .collect_with_indexer(HashIndexer::default())
+            .unwrap();
+        graph.orient(Orientation::Outward, None);
+        let approx = |a: R64, b: f64| (a.into_inner() - b).abs() < 1e-6;
+
+        // The cube has unit width and is centered at the origin.
+        assert!(approx(graph.surface_area(), 6.0));
+        assert!(approx(graph.volume(), 1.0));
+        let center = graph.center_of_mass().unwrap();
+        assert!(approx(center.x, 0.0) && approx(center.y, 0.0) && approx(center.z, 0.0));
+        let inertia = graph.inertia_tensor().unwrap();
+        for (i, row) in inertia.iter().enumerate() {
+            for (j, entry) in row.iter().enumerate() {
+                assert!(approx(*entry, if i == j { 1.0 / 6.0 } else { 0.0 }));
+            }
+        }
+    }
+
+    #[test]
+    fn corner_tangents() {
+        let graph = fixture::grid();
+        // Mirror the texture coordinates of the faces in the left column.
+        let is_mirrored = |face: FaceView<&MeshGraph<Point3<f64>>>| {
+            face.adjacent_vertices()
+                .all(|vertex| vertex.position().x <= 1.0)
+        };
+        let tangents = graph
+            .corner_tangents_with(|face, vertex| {
+                let position = vertex.position();
+                let u = if is_mirrored(face) {
+                    -position.x
+                }
+                else {
+                    position.x
+                };
+                (Vector3::z(), (u, position.y))
+            })
+            .unwrap();
+
+        assert_eq!(16, tangents.len());
+        for face in graph.faces() {
+            let sign = if is_mirrored(face) { -1.0 } else { 1.0 };
+            for vertex in face.adjacent_vertices() {
+                let tangent = tangents[&(face.key(), vertex.key())];
+                assert!((tangent.vector - (Vector3::x() * sign)).norm() < 1e-9);
+                assert!((tangent.sign - sign).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn corner_tangents_mirrored_seam() {
+        let graph = fixture::grid();
+        // Mirror the texture coordinates about the center column of vertices,
+        // which share both normals and texture coordinates across the seam.
+        let is_mirrored = |face: FaceView<&MeshGraph<Point3<f64>>>| {
+            face.adjacent_vertices()
+                .all(|vertex| vertex.position().x >= 1.0)
+        };
+        let tangents = graph
+            .corner_tangents_with(|_, vertex| {
+                let position = vertex.position();
+                (Vector3::z(), (1.0 - (position.x - 1.0).abs(), position.y))
+            })
+            .unwrap();
+
+        for face in graph.faces() {
+            let sign = if is_mirrored(face) { -1.0 } else { 1.0 };
+            for vertex in face.adjacent_vertices() {
+                let tangent = tangents[&(face.key(), vertex.key())];
+                assert!((tangent.vector - (Vector3::x() * sign)).norm() < 1e-9);
+                assert!((tangent.sign - sign).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn planarize() {
+        // A grid of four quadrilaterals with a raised interior vertex and
+        // corner.
+        let grid = |raised: &[usize]| {
+            fixture::grid_with(|index| {
+                if raised.contains(&index) {
+                    0.5
+                }
+                else {
+                    0.0
+                }
+            })
+        };
+
+        let mut graph = grid(&[4, 8]);
+        assert!(graph.planarize(1e-3, 1000).unwrap() <= 1e-3);
+        for face in graph.faces() {
+            let plane = face.plane().unwrap();
+            for vertex in face.adjacent_vertices() {
+                let distance = (*vertex.position() - plane.origin).dot(plane.normal.get());
+                assert!(distance.abs() <= 1e-3);
+            }
+        }
+
+        // Keep the boundary fixed. Only the interior vertex may move.
+        let mut graph = grid(&[4]);
+        let boundary = graph
+            .vertices()
+            .filter(|vertex| (*vertex.position() - Point3::new(1.0, 1.0, 0.5)).norm() > 1e-9)
+            .map(|vertex| (vertex.key(), *vertex.position()))
+            .collect::<Vec<_>>();
+        let deviation = graph
+            .planarize_with(1e-6, 1000, |vertex| {
+                vertex
+                    .outgoing_arcs()
+                    .any(|arc| arc.is_boundary_arc() || arc.opposite_arc().is_boundary_arc())
+            })
+            .unwrap();
+        assert!(deviation <= 1e-6);
+        for (key, position) in boundary {
+            assert!((position - *graph.vertex(key).unwrap().position()).norm() < 1e-9);
+        }
+        let center = graph
+            .vertices()
+            .find(|vertex| {
+                let position = vertex.position();
+                (position.x - 1.0).abs() < 1e-9 && (position.y - 1.0).abs() < 1e-9
+            })
+            .unwrap();
+        assert!(center.position().z.abs() <= 1e-6);
+    }
+
+    #[test]
+    fn to_mesh_by_crease() {
+        let graph = fixture::grid();
+        let buffer: MeshBuffer4<usize, (Point3<f64>, Vector3<f64>)> = graph
+            .to_mesh_by_crease_with(
+                0.1,
+                NormalWeight::Angle,
+                |_| false,
+                |vertex, normal| (*vertex.position(), normal),
+            )
+            .unwrap();
+        assert_eq!(9, buffer.as_vertex_slice().len());
+
+        // Split the vertices along the edges in the middle column.
+        let buffer: MeshBuffer4<usize, (Point3<f64>, Vector3<f64>)> = graph
+            .to_mesh_by_crease_with(
+                0.1,
+                NormalWeight::Angle,
+                |edge| {
+                    let arc = edge.into_arc();
+                    (arc.source_vertex().position().x - 1.0).abs() < 1e-9
+                        && (arc.destination_vertex().position().x - 1.0).abs() < 1e-9
+                },
+                |vertex, normal| (*vertex.position(), normal),
+            )
+            .unwrap();
+        assert_eq!(12, buffer.as_vertex_slice().len());
+        for (_, normal) in buffer.as_vertex_slice() {
+            assert!((normal - Vector3::z()).norm() < 1e-9);
+        }
+
+        let graph: MeshGraph<Point3<f64>> = Cube::new().polygons::<Position<E3>>().collect();
+        let buffer: MeshBuffer4<usize, (Point3<f64>, Vector3<f64>)> = graph
+            .to_mesh_by_crease_with(
+                0.5,
+                NormalWeight::Angle,
+                |_| false,
+                |vertex, normal| (*vertex.position(), normal),
+            )
+            .unwrap();
+        assert_eq!(24, buffer.as_vertex_slice().len());
+        let buffer: MeshBuffer4<usize, (Point3<f64>, Vector3<f64>)> = graph
+            .to_mesh_by_crease_with(
+                std::f64::consts::PI,
+                NormalWeight::Angle,
+                |_| false,
+                |vertex, normal| (*vertex.position(), normal),
+            )
+            .unwrap();
+        assert_eq!(8, buffer.as_vertex_slice().len());
+    }
+
+    #[test]
+    fn repair_non_manifold() {
+        // Construct the same "fan" of three triangles as above, but wind the
+        // last triangle inconsistently with its neighbors.
+        let (graph, report) = MeshGraph::<Point3<i32>>::from_raw_buffers_with_repair(
+            vec![NGon([0u32, 1, 2]), NGon([1, 0, 3]), NGon([0, 1, 4])],
+            vec![(0, 0, 1), (0, 0, -1), (1, 0, 0), (0, 1, 0), (1, 1, 0)],
+        )
+        .unwrap();
+
+        assert_eq!(3, graph.face_count());
+        assert_eq!(1, report.split_edges().len());
+        assert!(graph.validate().is_valid());
+    }
+
     // This test is a sanity check for iterators over orphan views and the
     // unsafe transmutations used to coerce lifetimes.
     #[test]
@@ -506,6 +506,85 @@ impl FaceExtrudeCache {
     }
 }
 
+pub struct FaceReverseCache {
+    arcs: Vec<(ArcKey, ArcKey, Option<FaceKey>)>,
+    faces: Vec<(FaceKey, ArcKey)>,
+}
+
+impl FaceReverseCache {
+    /// Caches the reversal of the face.
+    ///
+    /// Only a face that shares no arcs (including boundary arcs) with other
+    /// faces can be reversed in isolation, because the winding of adjacent
+    /// faces would otherwise be inconsistent.
+    pub fn from_face<B>(face: FaceView<B>) -> Result<Self, GraphError>
+    where
+        B: Reborrow,
+        B::Target: AsStorage<Arc<Data<B>>>
+            + AsStorage<Face<Data<B>>>
+            + AsStorage<Vertex<Data<B>>>
+            + Consistent
+            + Parametric,
+    {
+        let cache = FaceReverseCache::from_component(face)?;
+        if cache.faces.len() != 1 {
+            Err(GraphError::TopologyConflict)
+        }
+        else {
+            Ok(cache)
+        }
+    }
+
+    /// Caches the reversal of all faces that are connected to the face.
+    pub fn from_component<B>(face: FaceView<B>) -> Result<Self, GraphError>
+    where
+        B: Reborrow,
+        B::Target: AsStorage<Arc<Data<B>>>
+            + AsStorage<Face<Data<B>>>
+            + AsStorage<Vertex<Data<B>>>
+            + Consistent
+            + Parametric,
+    {
+        // Collect the arcs that are reachable from the face via next, previous,
+        // and opposite arcs. The winding of all of these arcs must be reversed
+        // together to maintain consistency.
+        let mut keys = HashSet::new();
+        let mut buffer = vec![face.arc().key()];
+        while let Some(ab) = buffer.pop() {
+            if keys.insert(ab) {
+                let arc = Rebind::<_, ArcView<_>>::rebind(face.to_ref(), ab)
+                    .ok_or(GraphError::TopologyMalformed)?;
+                buffer.push(arc.next_arc().key());
+                buffer.push(arc.previous_arc().key());
+                buffer.push(arc.opposite_arc().key());
+            }
+        }
+        let mut arcs = Vec::with_capacity(keys.len());
+        let mut faces = HashMap::new();
+        for ab in keys {
+            let arc = Rebind::<_, ArcView<_>>::rebind(face.to_ref(), ab)
+                .ok_or(GraphError::TopologyMalformed)?;
+            // The reversed arc takes the face of its opposite arc. Its next arc
+            // is the opposite of the previous arc of its opposite arc.
+            let opposite = arc.opposite_arc();
+            arcs.push((
+                ab,
+                opposite.previous_arc().key().into_opposite(),
+                opposite.face().map(|face| face.key()),
+            ));
+            if let Some(face) = arc.face() {
+                faces
+                    .entry(face.key())
+                    .or_insert_with(|| face.arc().key().into_opposite());
+            }
+        }
+        Ok(FaceReverseCache {
+            arcs,
+            faces: faces.into_iter().collect(),
+        })
+    }
+}
+
 // TODO: Should this accept arc data at all?
 pub fn insert_with<N, P, F>(
     mut mutation: N,
@@ -683,3 +762,25 @@ where
     }
     Ok(extrusion)
 }
+
+pub fn reverse<N, P>(mut mutation: N, cache: FaceReverseCache) -> Result<(), GraphError>
+where
+    N: AsMut<Mutation<P>>,
+    P: Mode,
+    P::Graph: Mutable,
+{
+    let FaceReverseCache { arcs, faces } = cache;
+    for (ab, bc, abc) in arcs {
+        mutation.as_mut().connect_adjacent_arcs(ab, bc)?;
+        match abc {
+            Some(abc) => mutation.as_mut().connect_arc_to_face(ab, abc)?,
+            None => {
+                mutation.as_mut().disconnect_arc_from_face(ab)?;
+            }
+        }
+    }
+    for (abc, ab) in faces {
+        mutation.as_mut().connect_face_to_arc(ab, abc)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,603 @@
+//! Surface parameterization.
+//!
+//! Parameterizations map the vertices of a disk-like graph into the plane by
+//! solving sparse linear systems. These systems are symmetric and are solved
+//! using the conjugate gradient method, so no linear algebra dependencies are
+//! required.
+
+use decorum::Real;
+use num::{One, Zero};
+use std::collections::{HashMap, HashSet};
+use theon::space::{EuclideanSpace, InnerSpace, Scalar};
+use theon::AsPosition;
+
+use crate::graph::data::GraphData;
+use crate::graph::geometry::{self, VertexPosition};
+use crate::graph::topology;
+use crate::graph::vertex::VertexKey;
+use crate::graph::{GraphError, MeshGraph, OptionExt as _};
+
+type Uv<T> = (T, T);
+
+/// Parameterization method.
+///
+/// See [`MeshGraph::parameterize`].
+///
+/// [`MeshGraph::parameterize`]: crate::graph::MeshGraph::parameterize
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Parameterization {
+    /// Tutte embedding.
+    ///
+    /// Each interior vertex is placed at the mean of its adjacent vertices.
+    /// The embedding has no fold-overs if the boundary is pinned to a convex
+    /// shape, but it may significantly distort angles and areas.
+    Tutte,
+    /// Harmonic map.
+    ///
+    /// Like the Tutte embedding, but adjacent vertices are weighted by the
+    /// cotangents of the angles opposite their edges. This reduces distortion
+    /// of triangulated surfaces, but may fold over if there are obtuse
+    /// triangles.
+    Harmonic,
+    /// Least squares conformal map (LSCM).
+    ///
+    /// Minimizes the deviation from a conformal (angle preserving) map over
+    /// all faces. Faces are triangulated as fans about their first vertex and
+    /// should be convex.
+    Conformal,
+}
+
+/// Pinning of the boundary of a parameterization.
+///
+/// See [`MeshGraph::parameterize`].
+///
+/// [`MeshGraph::parameterize`]: crate::graph::MeshGraph::parameterize
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Pinning {
+    /// Pins the boundary to the circle inscribed in the unit square.
+    ///
+    /// Boundary vertices are spaced by arc length.
+    Circle,
+    /// Pins the boundary to the perimeter of the unit square.
+    ///
+    /// Boundary vertices are spaced by arc length.
+    Square,
+    /// Pins only the two boundary vertices that are farthest apart. The
+    /// remaining boundary is free and the resulting coordinates are scaled
+    /// uniformly to fit the unit square.
+    ///
+    /// This is only supported by [`Parameterization::Conformal`].
+    ///
+    /// [`Parameterization::Conformal`]: crate::graph::Parameterization::Conformal
+    Extrema,
+}
+
+/// Computes planar coordinates for each vertex in the disk-like sub-graph that
+/// includes the given vertex.
+pub(in crate::graph) fn parameterize<G>(
+    graph: &MeshGraph<G>,
+    key: VertexKey,
+    method: Parameterization,
+    pinning: Pinning,
+) -> Result<HashMap<VertexKey, Uv<Scalar<VertexPosition<G>>>>, GraphError>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace,
+{
+    let invariants = topology::invariants(graph, key);
+    if invariants.boundary_count() != 1 || invariants.genus() != Some(0) {
+        return Err(GraphError::TopologyMalformed);
+    }
+    let vertices = graph
+        .vertex(key)
+        .expect_consistent()
+        .traverse_by_depth()
+        .map(|vertex| vertex.key())
+        .collect::<Vec<_>>();
+    let mut boundary = vertices
+        .iter()
+        .flat_map(|key| graph.vertex(*key).expect_consistent().into_outgoing_arcs())
+        .find(|arc| arc.is_boundary_arc())
+        .expect_consistent()
+        .ring()
+        .arcs()
+        .map(|arc| arc.source_vertex())
+        .map(|vertex| (vertex.key(), *vertex.position()))
+        .collect::<Vec<_>>();
+    // The ring of a boundary arc runs opposite to the winding of faces.
+    // Reverse it so that the boundary is pinned counter-clockwise and faces
+    // are not mirrored.
+    boundary.reverse();
+    let pins = pin(&boundary, pinning)?;
+    match method {
+        Parameterization::Tutte | Parameterization::Harmonic => {
+            if pinning == Pinning::Extrema {
+                return Err(GraphError::Geometry);
+            }
+            fixed(graph, &vertices, pins, method == Parameterization::Harmonic)
+        }
+        Parameterization::Conformal => {
+            let uvs = conformal(graph, &vertices, pins)?;
+            Ok(if pinning == Pinning::Extrema {
+                normalize(uvs)
+            }
+            else {
+                uvs
+            })
+        }
+    }
+}
+
+/// Pins boundary vertices to planar coordinates.
+fn pin<S>(
+    boundary: &[(VertexKey, S)],
+    pinning: Pinning,
+) -> Result<HashMap<VertexKey, Uv<Scalar<S>>>, GraphError>
+where
+    S: EuclideanSpace,
+{
+    let zero = Scalar::<S>::zero();
+    let one = Scalar::<S>::one();
+    let two = one + one;
+    if let Pinning::Extrema = pinning {
+        let mut extrema = (0, 0, zero);
+        for (i, (_, a)) in boundary.iter().enumerate() {
+            for (j, (_, b)) in boundary.iter().enumerate().skip(i + 1) {
+                let distance = (*b - *a).magnitude();
+                if distance > extrema.2 {
+                    extrema = (i, j, distance);
+                }
+            }
+        }
+        if extrema.2 <= zero {
+            return Err(GraphError::Geometry);
+        }
+        return Ok(vec![
+            (boundary[extrema.0].0, (zero, zero)),
+            (boundary[extrema.1].0, (one, zero)),
+        ]
+        .into_iter()
+        .collect());
+    }
+    let mut lengths = Vec::with_capacity(boundary.len());
+    let mut perimeter = zero;
+    for (i, (_, a)) in boundary.iter().enumerate() {
+        lengths.push(perimeter);
+        perimeter = perimeter + (boundary[(i + 1) % boundary.len()].1 - *a).magnitude();
+    }
+    if perimeter <= zero {
+        return Err(GraphError::Geometry);
+    }
+    let half = one / two;
+    let pi = Real::acos(-one);
+    Ok(boundary
+        .iter()
+        .zip(lengths)
+        .map(|((key, _), length)| {
+            let t = length / perimeter;
+            let uv = if let Pinning::Circle = pinning {
+                let theta = two * pi * t;
+                (
+                    half + (half * Real::cos(theta)),
+                    half + (half * Real::sin(theta)),
+                )
+            }
+            else {
+                let s = (two + two) * t;
+                if s < one {
+                    (s, zero)
+                }
+                else if s < two {
+                    (one, s - one)
+                }
+                else if s < two + one {
+                    ((two + one) - s, one)
+                }
+                else {
+                    (zero, (two + two) - s)
+                }
+            };
+            (*key, uv)
+        })
+        .collect())
+}
+
+/// Computes a Tutte embedding or harmonic map with a fixed boundary.
+fn fixed<G>(
+    graph: &MeshGraph<G>,
+    vertices: &[VertexKey],
+    pins: HashMap<VertexKey, Uv<Scalar<VertexPosition<G>>>>,
+    is_cotangent: bool,
+) -> Result<HashMap<VertexKey, Uv<Scalar<VertexPosition<G>>>>, GraphError>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace,
+{
+    let zero = Scalar::<VertexPosition<G>>::zero();
+    let one = Scalar::<VertexPosition<G>>::one();
+    let two = one + one;
+    let free = unpinned(vertices, &pins);
+    let mut matrix = vec![HashMap::new(); free.len()];
+    let mut bu = vec![zero; free.len()];
+    let mut bv = vec![zero; free.len()];
+    for (key, i) in &free {
+        let vertex = graph.vertex(*key).expect_consistent();
+        let p = *vertex.position();
+        for arc in vertex.outgoing_arcs() {
+            let adjacent = arc.destination_vertex();
+            let weight = if is_cotangent {
+                // Weight the edge by the cotangents of the angles opposite the
+                // edge in its faces.
+                let q = *adjacent.position();
+                let mut weight = zero;
+                if !arc.is_boundary_arc() {
+                    let r = *arc.next_arc().destination_vertex().position();
+                    weight = weight + geometry::cotangent(p - r, q - r)?;
+                }
+                let opposite = arc.opposite_arc();
+                if !opposite.is_boundary_arc() {
+                    let r = *opposite.next_arc().destination_vertex().position();
+                    weight = weight + geometry::cotangent(q - r, p - r)?;
+                }
+                weight / two
+            }
+            else {
+                one
+            };
+            add(&mut matrix[*i], *i, weight);
+            if let Some((u, v)) = pins.get(&adjacent.key()) {
+                bu[*i] = bu[*i] + (weight * *u);
+                bv[*i] = bv[*i] + (weight * *v);
+            }
+            else {
+                add(&mut matrix[*i], free[&adjacent.key()], -weight);
+            }
+        }
+    }
+    let u = solve(&matrix, &bu)?;
+    let v = solve(&matrix, &bv)?;
+    Ok(pins
+        .into_iter()
+        .chain(free.into_iter().map(|(key, i)| (key, (u[i], v[i]))))
+        .collect())
+}
+
+/// Computes a least squares conformal map.
+fn conformal<G>(
+    graph: &MeshGraph<G>,
+    vertices: &[VertexKey],
+    pins: HashMap<VertexKey, Uv<Scalar<VertexPosition<G>>>>,
+) -> Result<HashMap<VertexKey, Uv<Scalar<VertexPosition<G>>>>, GraphError>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace,
+{
+    let zero = Scalar::<VertexPosition<G>>::zero();
+    let free = unpinned(vertices, &pins);
+    // The coordinates of each free vertex are interleaved.
+    let mut matrix = vec![HashMap::new(); 2 * free.len()];
+    let mut y = vec![zero; 2 * free.len()];
+    let faces = vertices
+        .iter()
+        .flat_map(|key| graph.vertex(*key).expect_consistent().into_adjacent_faces())
+        .map(|face| face.key())
+        .collect::<HashSet<_>>();
+    for face in faces
+        .into_iter()
+        .map(|key| graph.face(key).expect_consistent())
+    {
+        let ring = face
+            .adjacent_vertices()
+            .map(|vertex| (vertex.key(), *vertex.position()))
+            .collect::<Vec<_>>();
+        let (k0, p0) = ring[0];
+        for pair in ring[1..].windows(2) {
+            let ((k1, p1), (k2, p2)) = (pair[0], pair[1]);
+            // Express the triangle in an orthonormal basis of its plane, with
+            // its first vertex at the origin and its second vertex on the
+            // horizontal axis.
+            let e1 = p1 - p0;
+            let e2 = p2 - p0;
+            let x1 = e1.magnitude();
+            if x1 <= zero {
+                return Err(GraphError::Geometry);
+            }
+            let x2 = e2.dot(e1) / x1;
+            let y2 = (e2.dot(e2)) - (x2 * x2);
+            let y2 = Real::sqrt(if y2 < zero { zero } else { y2 });
+            let area = x1 * y2;
+            if area <= zero {
+                return Err(GraphError::Geometry);
+            }
+            // The edges opposite each vertex scaled by the area of the
+            // triangle. A map is conformal if and only if it satisfies the
+            // Cauchy-Riemann equations, which are linear in these edges.
+            let scale = Real::sqrt(area);
+            let edges = [
+                ((x2 - x1) / scale, y2 / scale),
+                (-x2 / scale, -y2 / scale),
+                (x1 / scale, zero),
+            ];
+            let mut rows = [(Vec::with_capacity(6), zero), (Vec::with_capacity(6), zero)];
+            for (key, (a, b)) in [k0, k1, k2].iter().zip(edges.iter()) {
+                if let Some((u, v)) = pins.get(key) {
+                    rows[0].1 = rows[0].1 - ((*a * *u) - (*b * *v));
+                    rows[1].1 = rows[1].1 - ((*b * *u) + (*a * *v));
+                }
+                else {
+                    let i = 2 * free[key];
+                    rows[0].0.extend_from_slice(&[(i, *a), (i + 1, -*b)]);
+                    rows[1].0.extend_from_slice(&[(i, *b), (i + 1, *a)]);
+                }
+            }
+            // Accumulate the normal equations of the least squares problem.
+            for (entries, rhs) in rows.iter() {
+                for (i, x) in entries.iter() {
+                    y[*i] = y[*i] + (*x * *rhs);
+                    for (j, y) in entries.iter() {
+                        add(&mut matrix[*i], *j, *x * *y);
+                    }
+                }
+            }
+        }
+    }
+    let x = solve(&matrix, &y)?;
+    Ok(pins
+        .into_iter()
+        .chain(
+            free.into_iter()
+                .map(|(key, i)| (key, (x[2 * i], x[(2 * i) + 1]))),
+        )
+        .collect())
+}
+
+/// Indexes the vertices that are not pinned.
+fn unpinned<T>(vertices: &[VertexKey], pins: &HashMap<VertexKey, T>) -> HashMap<VertexKey, usize> {
+    vertices
+        .iter()
+        .cloned()
+        .filter(|key| !pins.contains_key(key))
+        .enumerate()
+        .map(|(i, key)| (key, i))
+        .collect()
+}
+
+/// Uniformly scales and translates coordinates to fit the unit square.
+fn normalize<T>(uvs: HashMap<VertexKey, Uv<T>>) -> HashMap<VertexKey, Uv<T>>
+where
+    T: Real,
+{
+    let mut uvs = uvs.into_iter();
+    let (key, (u, v)) = if let Some(uv) = uvs.next() {
+        uv
+    }
+    else {
+        return HashMap::new();
+    };
+    let mut minimum = (u, v);
+    let mut maximum = (u, v);
+    let uvs = Some((key, (u, v)))
+        .into_iter()
+        .chain(uvs)
+        .inspect(|(_, (u, v))| {
+            if *u < minimum.0 {
+                minimum.0 = *u;
+            }
+            if *v < minimum.1 {
+                minimum.1 = *v;
+            }
+            if *u > maximum.0 {
+                maximum.0 = *u;
+            }
+            if *v > maximum.1 {
+                maximum.1 = *v;
+            }
+        })
+        .collect::<Vec<_>>();
+    let extent = maximum.0 - minimum.0;
+    let extent = if maximum.1 - minimum.1 > extent {
+        maximum.1 - minimum.1
+    }
+    else {
+        extent
+    };
+    let scale = if extent > T::zero() {
+        T::one() / extent
+    }
+    else {
+        T::one()
+    };
+    uvs.into_iter()
+        .map(|(key, (u, v))| (key, ((u - minimum.0) * scale, (v - minimum.1) * scale)))
+        .collect()
+}
+
+fn add<T>(row: &mut HashMap<usize, T>, column: usize, value: T)
+where
+    T: Real,
+{
+    let entry = row.entry(column).or_insert_with(T::zero);
+    *entry = *entry + value;
+}
+
+/// Solves a sparse symmetric positive-definite linear system using the
+/// conjugate gradient method.
+///
+/// # Errors
+///
+/// Returns an error if the system is not positive-definite (such as when
+/// cotangent weights are negative) or the method fails to converge.
+fn solve<T>(matrix: &[HashMap<usize, T>], b: &[T]) -> Result<Vec<T>, GraphError>
+where
+    T: Real,
+{
+    let zero = T::zero();
+    let one = T::one();
+    let ten = (0..10).fold(zero, |ten, _| ten + one);
+    let dot = |a: &[T], b: &[T]| a.iter().zip(b).fold(zero, |sum, (a, b)| sum + (*a * *b));
+    let mut x = vec![zero; b.len()];
+    let mut r = b.to_vec();
+    let mut p = r.clone();
+    let mut gamma = dot(&r, &r);
+    // Stop when the norm of the residual has been reduced by a factor of
+    // about 10^12.
+    let tolerance = gamma * (0..24).fold(one, |epsilon, _| epsilon / ten);
+    for _ in 0..(10 * b.len()) {
+        if gamma <= tolerance {
+            return Ok(x);
+        }
+        let q = matrix
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .fold(zero, |sum, (j, entry)| sum + (*entry * p[*j]))
+            })
+            .collect::<Vec<_>>();
+        let curvature = dot(&p, &q);
+        if curvature <= zero {
+            return Err(GraphError::Geometry);
+        }
+        let alpha = gamma / curvature;
+        for (x, p) in x.iter_mut().zip(p.iter()) {
+            *x = *x + (alpha * *p);
+        }
+        for (r, q) in r.iter_mut().zip(q.iter()) {
+            *r = *r - (alpha * *q);
+        }
+        let next = dot(&r, &r);
+        let beta = next / gamma;
+        for (p, r) in p.iter_mut().zip(r.iter()) {
+            *p = *r + (beta * *p);
+        }
+        gamma = next;
+    }
+    if gamma <= tolerance {
+        Ok(x)
+    }
+    else {
+        Err(GraphError::Geometry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Point3;
+
+    use crate::graph::{fixture, GraphError, MeshGraph, Parameterization, Pinning};
+    use crate::prelude::*;
+    use crate::primitive::cube::Cube;
+    use crate::primitive::generate::Position;
+
+    #[test]
+    fn tutte_parameterization() {
+        let graph = fixture::grid();
+        let uvs = graph
+            .parameterize(Parameterization::Tutte, Pinning::Square)
+            .unwrap();
+
+        assert_eq!(9, uvs.len());
+        for vertex in graph.vertices() {
+            let (u, v) = uvs[&vertex.key()];
+            assert!((0.0..=1.0).contains(&u) && (0.0..=1.0).contains(&v));
+        }
+        // The interior vertex is placed at the center by symmetry.
+        let center = graph
+            .vertices()
+            .find(|vertex| *vertex.position() == Point3::new(1.0, 1.0, 0.0))
+            .unwrap();
+        let (u, v) = uvs[&center.key()];
+        assert!((u - 0.5).abs() < 1e-6 && (v - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn harmonic_parameterization() {
+        let mut graph = fixture::grid();
+        graph.triangulate();
+        let uvs = graph
+            .parameterize(Parameterization::Harmonic, Pinning::Circle)
+            .unwrap();
+
+        for vertex in graph.vertices() {
+            let (u, v) = uvs[&vertex.key()];
+            let radius = ((u - 0.5).powi(2) + (v - 0.5).powi(2)).sqrt();
+            if *vertex.position() == Point3::new(1.0, 1.0, 0.0) {
+                assert!(radius < 0.5);
+            }
+            else {
+                assert!((radius - 0.5).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn conformal_parameterization() {
+        let graph = fixture::grid();
+        let uvs = graph
+            .parameterize(Parameterization::Conformal, Pinning::Extrema)
+            .unwrap();
+
+        // The graph is planar, so its conformal map is a similarity and all
+        // edges have the same length.
+        let lengths = graph
+            .edges()
+            .map(|edge| {
+                let arc = edge.into_arc();
+                let (a, b) = (
+                    uvs[&arc.source_vertex().key()],
+                    uvs[&arc.destination_vertex().key()],
+                );
+                ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+            })
+            .collect::<Vec<_>>();
+        assert!(lengths
+            .iter()
+            .all(|length| (length - lengths[0]).abs() < 1e-6));
+        assert!(uvs
+            .values()
+            .all(|(u, v)| (-1e-6..=1.0 + 1e-6).contains(u) && (-1e-6..=1.0 + 1e-6).contains(v)));
+    }
+
+    #[test]
+    fn parameterization_orientation() {
+        let mut graph = fixture::grid();
+        graph.triangulate();
+        for (method, pinning) in [
+            (Parameterization::Tutte, Pinning::Square),
+            (Parameterization::Harmonic, Pinning::Circle),
+            (Parameterization::Conformal, Pinning::Extrema),
+            (Parameterization::Conformal, Pinning::Circle),
+        ]
+        .iter()
+        .cloned()
+        {
+            let uvs = graph.parameterize(method, pinning).unwrap();
+            // The faces of the graph are wound counter-clockwise in the plane,
+            // so their signed areas in the chart must be positive.
+            for face in graph.faces() {
+                let ring = face
+                    .adjacent_vertices()
+                    .map(|vertex| uvs[&vertex.key()])
+                    .collect::<Vec<_>>();
+                let area = (0..ring.len()).fold(0.0, |area, i| {
+                    let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+                    area + ((a.0 * b.1) - (b.0 * a.1))
+                });
+                assert!(area > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn parameterize_closed_graph() {
+        let graph: MeshGraph<Point3<f64>> =
+            Cube::new().polygons::<Position<Point3<f64>>>().collect();
+
+        assert!(matches!(
+            graph.parameterize(Parameterization::Tutte, Pinning::Circle),
+            Err(GraphError::TopologyMalformed)
+        ));
+    }
+}
@@ -0,0 +1,310 @@
+//! Ray casting.
+//!
+//! Faces are intersected as triangulations of their rings, so non-triangular
+//! and non-planar faces are supported. For planar faces, including concave
+//! faces, the triangulation covers exactly the face.
+
+use num::{NumCast, One, Zero};
+use smallvec::SmallVec;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Formatter};
+use theon::ops::{Cross, Dot};
+use theon::query::Ray;
+use theon::space::{EuclideanSpace, FiniteDimensional, Scalar, Vector};
+use theon::AsPosition;
+use typenum::U3;
+
+use crate::geometry::bvh::Bvh;
+use crate::graph::data::GraphData;
+use crate::graph::face::{FaceKey, FaceView};
+use crate::graph::geometry::{self, VertexPosition};
+use crate::graph::vertex::VertexKey;
+use crate::graph::{GraphError, MeshGraph};
+
+/// Tolerance relative to the magnitudes of the edges of a triangle and the
+/// direction of a ray below which they are considered parallel.
+const TOLERANCE: f64 = 1e-7;
+
+/// Intersection of a ray and a face.
+#[derive(Clone)]
+pub struct RayHit<S>
+where
+    S: EuclideanSpace,
+{
+    /// The intersected face.
+    pub face: FaceKey,
+    /// The distance along the ray to the intersection.
+    pub distance: Scalar<S>,
+    /// Coordinates of the intersection with respect to the vertices of the
+    /// face in ring order.
+    ///
+    /// These generalized barycentric coordinates sum to one and interpolate
+    /// the position of the intersection from the positions of the vertices.
+    /// For triangles, these are the barycentric coordinates of the
+    /// intersection.
+    pub coordinates: Vec<(VertexKey, Scalar<S>)>,
+    /// The position of the intersection.
+    pub position: S,
+}
+
+impl<S> Debug for RayHit<S>
+where
+    S: Debug + EuclideanSpace,
+    Scalar<S>: Debug,
+{
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("RayHit")
+            .field("face", &self.face)
+            .field("distance", &self.distance)
+            .field("coordinates", &self.coordinates)
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+/// Intersects a ray with the faces of a graph.
+///
+/// If a hierarchy is given, then only the faces with bounds intersected by the
+/// ray are tested. Hits are sorted by distance.
+pub(in crate::graph) fn cast<G>(
+    graph: &MeshGraph<G>,
+    bvh: Option<&Bvh<FaceKey, VertexPosition<G>>>,
+    ray: &Ray<VertexPosition<G>>,
+) -> Result<Vec<RayHit<VertexPosition<G>>>, GraphError>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+{
+    let mut hits = if let Some(bvh) = bvh {
+        let endpoint = ray.origin + *ray.direction.get();
+        let mut keys = Vec::new();
+        bvh.traverse_with(
+            |bounds| bounds.entry(ray.origin, endpoint).is_some(),
+            |key| keys.push(*key),
+        );
+        let mut hits = Vec::with_capacity(keys.len());
+        for key in keys {
+            let face = graph.face(key).ok_or(GraphError::TopologyNotFound)?;
+            hits.extend(intersect(face, ray));
+        }
+        hits
+    }
+    else {
+        graph
+            .faces()
+            .filter_map(|face| intersect(face, ray))
+            .collect()
+    };
+    hits.sort_by(|a, b| {
+        a.distance
+            .partial_cmp(&b.distance)
+            .unwrap_or(Ordering::Equal)
+    });
+    Ok(hits)
+}
+
+/// Intersects a ray with a face.
+///
+/// Returns the closest intersection with any triangle of the triangulation of
+/// the face.
+fn intersect<G>(
+    face: FaceView<&MeshGraph<G>>,
+    ray: &Ray<VertexPosition<G>>,
+) -> Option<RayHit<VertexPosition<G>>>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+{
+    let (keys, positions): (SmallVec<[_; 8]>, SmallVec<[_; 8]>) = face
+        .adjacent_vertices()
+        .map(|vertex| (vertex.key(), *vertex.position()))
+        .unzip();
+    let direction = *ray.direction.get();
+    let mut closest = None;
+    for [a, b, c] in geometry::triangulate(&positions) {
+        let corners = [positions[a], positions[b], positions[c]];
+        if let Some((distance, u, v)) = triangle(ray.origin, direction, corners) {
+            closest = match closest {
+                Some((nearest, ..)) if nearest <= distance => closest,
+                _ => Some((distance, [a, b, c], u, v)),
+            };
+        }
+    }
+    let (distance, [a, b, c], u, v) = closest?;
+    let coordinates = keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| {
+            let weight = if index == a {
+                Scalar::<VertexPosition<G>>::one() - u - v
+            }
+            else if index == b {
+                u
+            }
+            else if index == c {
+                v
+            }
+            else {
+                Zero::zero()
+            };
+            (*key, weight)
+        })
+        .collect();
+    Some(RayHit {
+        face: face.key(),
+        distance,
+        coordinates,
+        position: ray.origin + (direction * distance),
+    })
+}
+
+/// Intersects a ray with a triangle.
+///
+/// Returns the time of impact and the barycentric coordinates of the second
+/// and third vertices of the triangle. Both sides of the triangle are
+/// intersected.
+fn triangle<S>(
+    origin: S,
+    direction: Vector<S>,
+    positions: [S; 3],
+) -> Option<(Scalar<S>, Scalar<S>, Scalar<S>)>
+where
+    S: EuclideanSpace,
+    Vector<S>: Cross<Output = Vector<S>>,
+{
+    let zero = Scalar::<S>::zero();
+    let one = Scalar::<S>::one();
+    let tolerance = <Scalar<S> as NumCast>::from(TOLERANCE).unwrap();
+    let [a, b, c] = positions;
+    let ab = b - a;
+    let ac = c - a;
+    let p = direction.cross(ac);
+    let determinant = ab.dot(p);
+    // The magnitude of the determinant is at most the product of the
+    // magnitudes of the edges and direction, with which it is compared.
+    let bound = ab.dot(ab) * ac.dot(ac) * direction.dot(direction) * tolerance * tolerance;
+    if determinant * determinant <= bound {
+        // The ray is parallel to the triangle (or the triangle is degenerate).
+        return None;
+    }
+    let inverse = one / determinant;
+    let s = origin - a;
+    let u = s.dot(p) * inverse;
+    if u < zero || u > one {
+        return None;
+    }
+    let q = s.cross(ab);
+    let v = direction.dot(q) * inverse;
+    if v < zero || u + v > one {
+        return None;
+    }
+    let distance = ac.dot(q) * inverse;
+    if distance < zero {
+        None
+    }
+    else {
+        Some((distance, u, v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{Point3, Vector3};
+
+    use crate::geometry::{Ray, Unit};
+    use crate::graph::MeshGraph;
+    use crate::prelude::*;
+    use crate::primitive::cube::Cube;
+    use crate::primitive::generate::Position;
+    use crate::primitive::NGon;
+
+    type E3 = Point3<f64>;
+
+    #[test]
+    fn cast_ray_through_cube() {
+        let graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+        let bvh = graph.bvh();
+        let ray = Ray {
+            origin: E3::new(0.25, 0.125, 2.0),
+            direction: Unit::try_from_inner(Vector3::new(0.0, 0.0, -1.0)).unwrap(),
+        };
+
+        let hits = graph.cast_ray_all(&ray);
+        assert_eq!(2, hits.len());
+        for (hit, z) in hits.iter().zip(&[0.5, -0.5]) {
+            assert!((hit.position - E3::new(0.25, 0.125, *z)).norm() < 1e-9);
+            let sum = hit
+                .coordinates
+                .iter()
+                .map(|(_, weight)| weight)
+                .sum::<f64>();
+            assert!((sum - 1.0).abs() < 1e-9);
+            // The coordinates interpolate the position of the hit.
+            let position = hit
+                .coordinates
+                .iter()
+                .fold(Vector3::zeros(), |sum, (key, weight)| {
+                    sum + (graph.vertex(*key).unwrap().position().coords * *weight)
+                });
+            assert!((position - hit.position.coords).norm() < 1e-9);
+        }
+        assert!((hits[0].distance - 1.5).abs() < 1e-9);
+        assert!((hits[1].distance - 2.5).abs() < 1e-9);
+
+        let hit = graph.cast_ray_by_bvh(&bvh, &ray).unwrap().unwrap();
+        assert_eq!(hits[0].face, hit.face);
+        assert_eq!(2, graph.cast_ray_all_by_bvh(&bvh, &ray).unwrap().len());
+
+        // Cast away from the cube.
+        let ray = Ray {
+            origin: E3::new(0.25, 0.125, 2.0),
+            direction: Unit::try_from_inner(Vector3::new(0.0, 0.0, 1.0)).unwrap(),
+        };
+        assert!(graph.cast_ray(&ray).is_none());
+        assert!(graph.cast_ray_by_bvh(&bvh, &ray).unwrap().is_none());
+    }
+
+    #[test]
+    fn cast_ray_through_concave_face() {
+        // An L-shaped face with a notch that contains the centroid of its
+        // vertices.
+        let graph = MeshGraph::<E3>::from_raw_buffers(
+            vec![NGon([0usize, 1, 2, 3, 4, 5])],
+            vec![
+                (0.0, 0.0, 0.0),
+                (4.0, 0.0, 0.0),
+                (4.0, 1.0, 0.0),
+                (1.0, 1.0, 0.0),
+                (1.0, 3.0, 0.0),
+                (0.0, 3.0, 0.0),
+            ],
+        )
+        .unwrap();
+
+        // Cast through the notch.
+        let ray = Ray {
+            origin: E3::new(1.5, 1.5, 1.0),
+            direction: Unit::try_from_inner(Vector3::new(0.0, 0.0, -1.0)).unwrap(),
+        };
+        assert!(graph.cast_ray(&ray).is_none());
+
+        let ray = Ray {
+            origin: E3::new(0.5, 2.0, 1.0),
+            direction: Unit::try_from_inner(Vector3::new(0.0, 0.0, -1.0)).unwrap(),
+        };
+        let hit = graph.cast_ray(&ray).unwrap();
+        assert!((hit.position - E3::new(0.5, 2.0, 0.0)).norm() < 1e-9);
+        let position = hit
+            .coordinates
+            .iter()
+            .fold(Vector3::zeros(), |sum, (key, weight)| {
+                sum + (graph.vertex(*key).unwrap().position().coords * *weight)
+            });
+        assert!((position - hit.position.coords).norm() < 1e-9);
+    }
+}
@@ -0,0 +1,538 @@
+//! Repair of non-manifold input topology.
+//!
+//! Graphs cannot represent non-manifold edges, so constructing a graph from raw
+//! buffers fails if an edge is shared by more than two faces or adjacent faces
+//! are wound inconsistently. A vertex that pinches together more than one fan
+//! of faces is accepted, but forms a non-manifold vertex that is reported by
+//! validation and that some operations cannot traverse. This module rewrites
+//! such input into an equivalent manifold form before it is inserted into a
+//! graph.
+//!
+//! Repair operates on indices alone and never considers geometry.
+
+use smallvec::SmallVec;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::graph::face::FaceKey;
+use crate::IteratorExt as _;
+
+pub type Perimeter = SmallVec<[usize; 4]>;
+
+// Faces about each undirected edge.
+type Edges = HashMap<(usize, usize), SmallVec<[usize; 2]>>;
+
+/// Report of changes made while repairing the topology of input buffers.
+///
+/// Faces and vertices are identified by their index in the input buffers.
+///
+/// See [`MeshGraph::from_raw_buffers_with_repair`].
+///
+/// [`MeshGraph::from_raw_buffers_with_repair`]: crate::graph::MeshGraph::from_raw_buffers_with_repair
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RepairReport {
+    discarded_faces: Vec<usize>,
+    flipped_faces: Vec<usize>,
+    non_orientable_faces: Vec<usize>,
+    split_edges: Vec<(usize, usize)>,
+    duplicated_vertices: Vec<usize>,
+    // Keys of the faces of non-orientable components once inserted into a
+    // graph. See `MeshGraph::orient`.
+    pub(in crate::graph) non_orientable_keys: Vec<FaceKey>,
+}
+
+impl RepairReport {
+    /// Returns `true` if the input was not changed.
+    pub fn is_unchanged(&self) -> bool {
+        self.discarded_faces.is_empty()
+            && self.flipped_faces.is_empty()
+            && self.non_orientable_faces.is_empty()
+            && self.split_edges.is_empty()
+            && self.duplicated_vertices.is_empty()
+    }
+
+    /// Gets the faces that were discarded because they are degenerate.
+    ///
+    /// A face is degenerate if it has fewer than three distinct vertices or
+    /// visits a vertex more than once. Consecutive repeated indices are
+    /// collapsed before this is determined.
+    pub fn discarded_faces(&self) -> &[usize] {
+        self.discarded_faces.as_slice()
+    }
+
+    /// Gets the faces that were flipped (had their winding reversed) to agree
+    /// with the orientation of adjacent faces.
+    pub fn flipped_faces(&self) -> &[usize] {
+        self.flipped_faces.as_slice()
+    }
+
+    /// Gets a face from each connected component that is non-orientable, such
+    /// as a Möbius strip.
+    ///
+    /// Faces in these components cannot all be wound consistently. The edges
+    /// along which their winding conflicts are split, so the faces are still
+    /// inserted into the graph. Such sub-graphs are reported as non-orientable
+    /// by [`MeshGraph::orient`] when given this report.
+    ///
+    /// [`MeshGraph::orient`]: crate::graph::MeshGraph::orient
+    pub fn non_orientable_faces(&self) -> &[usize] {
+        self.non_orientable_faces.as_slice()
+    }
+
+    /// Gets the non-manifold edges that were split.
+    ///
+    /// An edge is split once for each face in excess of a manifold edge. Such
+    /// a face is detached along that edge alone by duplicating the vertices of
+    /// the edge, so it remains joined to its other adjacent faces.
+    pub fn split_edges(&self) -> &[(usize, usize)] {
+        self.split_edges.as_slice()
+    }
+
+    /// Gets the vertices that were duplicated, once for each copy.
+    ///
+    /// Vertices are duplicated when splitting edges and at pinch points, where
+    /// each additional fan of faces receives its own copy of the vertex.
+    pub fn duplicated_vertices(&self) -> &[usize] {
+        self.duplicated_vertices.as_slice()
+    }
+}
+
+/// Manifold topology produced by [`repair`].
+pub struct Repair {
+    /// Perimeters of the faces, which index into `vertices`.
+    pub faces: Vec<Perimeter>,
+    /// Input index of each face in `faces`.
+    pub origins: Vec<usize>,
+    /// Input index of each vertex. Duplicated vertices appear more than once.
+    pub vertices: Vec<usize>,
+    /// Whether or not each face in `faces` is in a non-orientable component.
+    pub non_orientable: Vec<bool>,
+    pub report: RepairReport,
+}
+
+/// Repairs the given face perimeters, which index into a vertex buffer with
+/// the given number of vertices.
+///
+/// All indices must be in bounds.
+pub fn repair<I>(faces: I, count: usize) -> Repair
+where
+    I: IntoIterator<Item = Perimeter>,
+{
+    let mut report = RepairReport::default();
+    let (mut faces, origins) = discard_degenerate_faces(faces, &mut report);
+    let mut vertices = (0..count).collect::<Vec<_>>();
+    let seeds = orient(&mut faces, &origins, &mut report);
+    let non_orientable = seeds
+        .iter()
+        .map(|seed| report.non_orientable_faces.contains(&origins[*seed]))
+        .collect();
+    split_edges(&mut faces, &mut vertices, &mut report);
+    split_vertices(&mut faces, &mut vertices, &mut report);
+    Repair {
+        faces,
+        origins,
+        vertices,
+        non_orientable,
+        report,
+    }
+}
+
+fn discard_degenerate_faces<I>(faces: I, report: &mut RepairReport) -> (Vec<Perimeter>, Vec<usize>)
+where
+    I: IntoIterator<Item = Perimeter>,
+{
+    let mut perimeters = Vec::new();
+    let mut origins = Vec::new();
+    for (index, perimeter) in faces.into_iter().enumerate() {
+        // Collapse consecutive repeated indices, including across the end and
+        // beginning of the perimeter.
+        let mut collapsed = perimeter
+            .iter()
+            .cloned()
+            .perimeter()
+            .filter(|(a, b)| a != b)
+            .map(|(_, b)| b)
+            .collect::<Perimeter>();
+        if collapsed.is_empty() {
+            collapsed.extend(perimeter.iter().cloned().take(1));
+        }
+        let distinct = collapsed.iter().collect::<HashSet<_>>().len();
+        if collapsed.len() < 3 || distinct != collapsed.len() {
+            report.discarded_faces.push(index);
+        }
+        else {
+            perimeters.push(collapsed);
+            origins.push(index);
+        }
+    }
+    (perimeters, origins)
+}
+
+fn undirected(a: usize, b: usize) -> (usize, usize) {
+    if a < b {
+        (a, b)
+    }
+    else {
+        (b, a)
+    }
+}
+
+fn edges(faces: &[Perimeter]) -> Edges {
+    let mut edges = HashMap::<_, SmallVec<[usize; 2]>>::new();
+    for (face, perimeter) in faces.iter().enumerate() {
+        for (a, b) in perimeter.iter().cloned().perimeter() {
+            edges.entry(undirected(a, b)).or_default().push(face);
+        }
+    }
+    edges
+}
+
+// Flips faces so that faces sharing a manifold edge traverse that edge in
+// opposing directions. Each connected component is oriented to agree with the
+// first face encountered in that component. Non-orientable components cannot
+// be oriented in this way; they are reported and their conflicting edges are
+// split later. Returns the index of the first face of the component of each
+// face.
+fn orient(faces: &mut [Perimeter], origins: &[usize], report: &mut RepairReport) -> Vec<usize> {
+    let edges = edges(faces);
+    let mut seeds = vec![0; faces.len()];
+    let mut visited = vec![false; faces.len()];
+    let mut buffer = VecDeque::new();
+    for seed in 0..faces.len() {
+        if visited[seed] {
+            continue;
+        }
+        visited[seed] = true;
+        buffer.push_back(seed);
+        let mut is_orientable = true;
+        while let Some(face) = buffer.pop_front() {
+            seeds[face] = seed;
+            let arcs = faces[face]
+                .iter()
+                .cloned()
+                .perimeter()
+                .collect::<SmallVec<[_; 4]>>();
+            for (a, b) in arcs {
+                let adjacent = &edges[&undirected(a, b)];
+                if adjacent.len() != 2 {
+                    continue;
+                }
+                let other = if adjacent[0] == face {
+                    adjacent[1]
+                }
+                else {
+                    adjacent[0]
+                };
+                if other == face {
+                    continue;
+                }
+                let is_coincident = faces[other]
+                    .iter()
+                    .cloned()
+                    .perimeter()
+                    .any(|arc| arc == (a, b));
+                if visited[other] {
+                    // The adjacent face has already been oriented. If it
+                    // traverses the edge in the same direction, then the
+                    // component cannot be oriented consistently.
+                    if is_coincident {
+                        is_orientable = false;
+                    }
+                    continue;
+                }
+                visited[other] = true;
+                if is_coincident {
+                    faces[other].reverse();
+                    report.flipped_faces.push(origins[other]);
+                }
+                buffer.push_back(other);
+            }
+        }
+        if !is_orientable {
+            report.non_orientable_faces.push(origins[seed]);
+        }
+    }
+    seeds
+}
+
+// Splits edges that are shared by more than two faces or that are traversed in
+// the same direction by two faces. The first face along such an edge keeps it
+// along with the first face that traverses it in the opposite direction. Each
+// remaining face is detached from the edge alone: the fan of faces about one
+// of the vertices of the edge receives its own copy of that vertex, so faces
+// remain joined across their other edges. Any pinch point that this leaves at
+// the other vertex of the edge is split by `split_vertices`.
+fn split_edges(faces: &mut [Perimeter], vertices: &mut Vec<usize>, report: &mut RepairReport) {
+    let mut edges = edges(faces);
+    let mut conflicts = edges
+        .iter()
+        .filter(|(_, adjacent)| adjacent.len() > 1)
+        .map(|(edge, _)| *edge)
+        .collect::<Vec<_>>();
+    conflicts.sort_unstable();
+    for (a, b) in conflicts {
+        // Faces may have been detached from the edge by an earlier split.
+        let adjacent = edges[&(a, b)].clone();
+        if adjacent.len() < 2 {
+            continue;
+        }
+        let direction = traverses(faces, adjacent[0], a, b);
+        let opposite = adjacent
+            .iter()
+            .cloned()
+            .skip(1)
+            .find(|face| traverses(faces, *face, a, b) != direction);
+        for face in adjacent
+            .iter()
+            .cloned()
+            .skip(1)
+            .filter(|face| Some(*face) != opposite)
+        {
+            report.split_edges.push((vertices[a], vertices[b]));
+            // Prefer a vertex with a fan that does not wrap around to the
+            // faces that keep the edge. Detaching such a fan opens the edge
+            // and nothing else. Otherwise, only the face is detached.
+            let (key, detached) = [a, b]
+                .iter()
+                .cloned()
+                .find_map(|key| fan(faces, &edges, (a, b), face, key).map(|fan| (key, fan)))
+                .unwrap_or_else(|| (a, vec![face]));
+            let source = vertices[key];
+            vertices.push(source);
+            report.duplicated_vertices.push(source);
+            let copy = vertices.len() - 1;
+            for face in detached {
+                rename(faces, &mut edges, face, key, copy);
+            }
+        }
+    }
+}
+
+// Gets the fan of faces about the vertex `key` that includes `face` and is
+// bounded by the given edge. Faces are joined across edges incident to the
+// vertex that are shared by exactly two consistently wound faces. Returns
+// `None` if the fan reaches another face that includes the edge.
+fn fan(
+    faces: &[Perimeter],
+    edges: &Edges,
+    edge: (usize, usize),
+    face: usize,
+    key: usize,
+) -> Option<Vec<usize>> {
+    let mut fan = vec![face];
+    let mut visited = HashSet::new();
+    visited.insert(face);
+    let mut buffer = vec![face];
+    while let Some(face) = buffer.pop() {
+        for neighbor in neighbors(&faces[face], key).iter().cloned() {
+            if undirected(key, neighbor) == edge {
+                continue;
+            }
+            let adjacent = &edges[&undirected(key, neighbor)];
+            if adjacent.len() != 2
+                || traverses(faces, adjacent[0], key, neighbor)
+                    == traverses(faces, adjacent[1], key, neighbor)
+            {
+                continue;
+            }
+            for other in adjacent.iter().cloned() {
+                if !visited.insert(other) {
+                    continue;
+                }
+                if faces[other]
+                    .iter()
+                    .cloned()
+                    .perimeter()
+                    .any(|(x, y)| undirected(x, y) == edge)
+                {
+                    return None;
+                }
+                fan.push(other);
+                buffer.push(other);
+            }
+        }
+    }
+    Some(fan)
+}
+
+// Replaces the vertex `from` with `to` in the perimeter of a face.
+fn rename(faces: &mut [Perimeter], edges: &mut Edges, face: usize, from: usize, to: usize) {
+    for neighbor in neighbors(&faces[face], from).iter().cloned() {
+        if let Some(adjacent) = edges.get_mut(&undirected(from, neighbor)) {
+            adjacent.retain(|other| *other != face);
+        }
+        edges
+            .entry(undirected(to, neighbor))
+            .or_default()
+            .push(face);
+    }
+    for key in faces[face].iter_mut() {
+        if *key == from {
+            *key = to;
+        }
+    }
+}
+
+// Returns `true` if the face traverses the arc from `a` to `b`.
+fn traverses(faces: &[Perimeter], face: usize, a: usize, b: usize) -> bool {
+    faces[face]
+        .iter()
+        .cloned()
+        .perimeter()
+        .any(|arc| arc == (a, b))
+}
+
+// Gets the vertices that follow and precede the given vertex in a perimeter.
+fn neighbors(perimeter: &Perimeter, key: usize) -> [usize; 2] {
+    let n = perimeter.len();
+    let index = perimeter.iter().position(|x| *x == key).unwrap();
+    [perimeter[(index + 1) % n], perimeter[(index + n - 1) % n]]
+}
+
+// Duplicates vertices at pinch points. The faces about a vertex are grouped
+// into fans of faces that share an edge incident to the vertex. Every fan but
+// the first receives its own copy of the vertex.
+fn split_vertices(faces: &mut [Perimeter], vertices: &mut Vec<usize>, report: &mut RepairReport) {
+    let mut incident = HashMap::<usize, Vec<usize>>::new();
+    for (face, perimeter) in faces.iter().enumerate() {
+        for key in perimeter.iter() {
+            incident.entry(*key).or_default().push(face);
+        }
+    }
+    let mut keys = incident.keys().cloned().collect::<Vec<_>>();
+    keys.sort_unstable();
+    for key in keys {
+        let incident = &incident[&key];
+        if incident.len() < 2 {
+            continue;
+        }
+        // Map each neighboring vertex to the faces that share an edge with it.
+        let mut neighbors = HashMap::<usize, SmallVec<[usize; 2]>>::new();
+        for face in incident.iter().cloned() {
+            for neighbor in self::neighbors(&faces[face], key).iter().cloned() {
+                neighbors.entry(neighbor).or_default().push(face);
+            }
+        }
+        let mut fans = Vec::<Vec<usize>>::new();
+        let mut visited = HashSet::with_capacity(incident.len());
+        for seed in incident.iter().cloned() {
+            if !visited.insert(seed) {
+                continue;
+            }
+            let mut fan = vec![seed];
+            let mut buffer = vec![seed];
+            while let Some(face) = buffer.pop() {
+                for neighbor in self::neighbors(&faces[face], key).iter().cloned() {
+                    for adjacent in neighbors[&neighbor].iter().cloned() {
+                        if visited.insert(adjacent) {
+                            fan.push(adjacent);
+                            buffer.push(adjacent);
+                        }
+                    }
+                }
+            }
+            fans.push(fan);
+        }
+        for fan in fans.into_iter().skip(1) {
+            let source = vertices[key];
+            vertices.push(source);
+            report.duplicated_vertices.push(source);
+            let copy = vertices.len() - 1;
+            for face in fan {
+                for x in faces[face].iter_mut() {
+                    if *x == key {
+                        *x = copy;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Point3;
+    use smallvec::smallvec;
+
+    use crate::graph::repair::{self, Perimeter};
+    use crate::graph::{fixture, MeshGraph};
+    use crate::primitive::Trigon;
+
+    #[test]
+    fn flip_inconsistent_face() {
+        // Two triangles that share the edge `(1, 2)` in the same direction.
+        let faces: Vec<Perimeter> = vec![smallvec![0, 1, 2], smallvec![1, 2, 3]];
+        let repair = repair::repair(faces, 4);
+
+        assert_eq!(&[1], repair.report.flipped_faces());
+        assert!(repair.report.split_edges().is_empty());
+    }
+
+    #[test]
+    fn detect_non_orientable() {
+        // A triangulated Möbius strip.
+        let faces: Vec<Perimeter> = fixture::mobius_faces()
+            .into_iter()
+            .map(|trigon| trigon.into_iter().collect())
+            .collect();
+        let repair = repair::repair(faces, 6);
+
+        assert_eq!(1, repair.report.non_orientable_faces().len());
+        assert!(!repair.report.split_edges().is_empty());
+    }
+
+    #[test]
+    fn split_non_manifold_edge() {
+        // Three triangles that share the edge `(0, 1)`.
+        let faces: Vec<Perimeter> =
+            vec![smallvec![0, 1, 2], smallvec![1, 0, 3], smallvec![1, 0, 4]];
+        let repair = repair::repair(faces, 5);
+
+        assert_eq!(1, repair.report.split_edges().len());
+        assert_eq!(7, repair.vertices.len());
+    }
+
+    #[test]
+    fn split_non_manifold_edge_only() {
+        // A sheet of two triangles and a fin of two triangles that share the
+        // edge `(0, 1)`. The fin is detached from the sheet along that edge
+        // alone, so its triangles remain joined to each other and the split
+        // yields exactly one more boundary loop than the sheet.
+        let (graph, report) = MeshGraph::<Point3<f64>>::from_raw_buffers_with_repair(
+            vec![
+                Trigon::new(0usize, 1, 2),
+                Trigon::new(1, 0, 3),
+                Trigon::new(1, 0, 4),
+                Trigon::new(0, 5, 4),
+            ],
+            vec![
+                (0.0, 0.0, 0.0),
+                (1.0, 0.0, 0.0),
+                (0.5, 1.0, 0.0),
+                (0.5, -1.0, 0.0),
+                (0.5, 0.0, 1.0),
+                (-0.5, 0.0, 1.0),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(&[(0, 1)], report.split_edges());
+        assert_eq!(8, graph.vertex_count());
+        let invariants = graph.invariants();
+        assert_eq!(2, invariants.component_count());
+        assert_eq!(2, invariants.boundary_count());
+        for invariants in graph.disjoint_subgraph_invariants() {
+            assert_eq!(2, invariants.face_count());
+            assert_eq!(1, invariants.boundary_count());
+        }
+    }
+
+    #[test]
+    fn split_pinch_vertex() {
+        // Two triangles that share only the vertex `0`.
+        let faces: Vec<Perimeter> = vec![smallvec![0, 1, 2], smallvec![0, 3, 4]];
+        let repair = repair::repair(faces, 5);
+
+        assert_eq!(&[0], repair.report.duplicated_vertices());
+        assert_eq!(6, repair.vertices.len());
+    }
+}
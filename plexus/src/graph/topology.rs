@@ -0,0 +1,308 @@
+//! Topological invariants.
+
+use std::collections::HashSet;
+use std::iter::Sum;
+
+use crate::graph::data::GraphData;
+use crate::graph::edge::ArcKey;
+use crate::graph::vertex::VertexKey;
+use crate::graph::{MeshGraph, OptionExt as _};
+
+/// Topological invariants of a [`MeshGraph`] or one of its disjoint
+/// sub-graphs.
+///
+/// See [`MeshGraph::invariants`] and
+/// [`MeshGraph::disjoint_subgraph_invariants`].
+///
+/// Orientability is not an invariant of a graph: arcs are directed and belong
+/// to at most one face, so the faces of a graph are always wound consistently.
+/// Non-orientable input, such as a Möbius strip, is split into an orientable
+/// form when a graph is constructed and is instead reported by
+/// [`MeshGraph::orient`] given the [`RepairReport`] of that construction.
+///
+/// [`MeshGraph`]: crate::graph::MeshGraph
+/// [`MeshGraph::disjoint_subgraph_invariants`]: crate::graph::MeshGraph::disjoint_subgraph_invariants
+/// [`MeshGraph::invariants`]: crate::graph::MeshGraph::invariants
+/// [`MeshGraph::orient`]: crate::graph::MeshGraph::orient
+/// [`RepairReport`]: crate::graph::RepairReport
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Invariants {
+    vertex_count: usize,
+    edge_count: usize,
+    face_count: usize,
+    boundary_count: usize,
+    component_count: usize,
+    genus: Option<usize>,
+}
+
+impl Invariants {
+    /// Gets the number of vertices $V$.
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    /// Gets the number of edges $E$.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Gets the number of faces $F$.
+    pub fn face_count(&self) -> usize {
+        self.face_count
+    }
+
+    /// Gets the number of boundary loops.
+    ///
+    /// A boundary loop is a ring of arcs that are not part of any face, such
+    /// as the rim of a hole. Only arcs with an opposite arc that is part of a
+    /// face are considered, so edges that are not part of any face do not
+    /// form boundary loops.
+    pub fn boundary_count(&self) -> usize {
+        self.boundary_count
+    }
+
+    /// Gets the number of disjoint sub-graphs.
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
+    /// Gets the Euler characteristic $\chi=V-E+F$.
+    pub fn euler_characteristic(&self) -> isize {
+        self.vertex_count as isize - self.edge_count as isize + self.face_count as isize
+    }
+
+    /// Gets the genus (the number of handles).
+    ///
+    /// For a surface with $b$ boundary loops, the genus $g$ is given by
+    /// $\chi=2-2g-b$. The genus of a graph with more than one disjoint
+    /// sub-graph is the sum of the genera of its sub-graphs.
+    ///
+    /// Returns `None` if the genus is not well-defined (such as when a
+    /// sub-graph has edges that are not part of any face).
+    pub fn genus(&self) -> Option<usize> {
+        self.genus
+    }
+
+    /// Returns `true` if the surface has no boundaries.
+    pub fn is_closed(&self) -> bool {
+        self.boundary_count == 0
+    }
+}
+
+impl Sum for Invariants {
+    fn sum<I>(invariants: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+    {
+        invariants.fold(
+            Invariants {
+                genus: Some(0),
+                ..Default::default()
+            },
+            |sum, invariants| Invariants {
+                vertex_count: sum.vertex_count + invariants.vertex_count,
+                edge_count: sum.edge_count + invariants.edge_count,
+                face_count: sum.face_count + invariants.face_count,
+                boundary_count: sum.boundary_count + invariants.boundary_count,
+                component_count: sum.component_count + invariants.component_count,
+                genus: sum
+                    .genus
+                    .and_then(|genus| invariants.genus.map(|other| genus + other)),
+            },
+        )
+    }
+}
+
+/// Computes the invariants of the disjoint sub-graph that includes the given
+/// vertex.
+pub(in crate::graph) fn invariants<G>(graph: &MeshGraph<G>, key: VertexKey) -> Invariants
+where
+    G: GraphData,
+{
+    let mut vertex_count = 0;
+    let mut arcs = HashSet::new();
+    let mut faces = HashSet::new();
+    for vertex in graph.vertex(key).expect_consistent().traverse_by_depth() {
+        vertex_count += 1;
+        for arc in vertex.outgoing_arcs() {
+            arcs.insert(arc.key());
+            if let Some(face) = arc.face() {
+                faces.insert(face.key());
+            }
+        }
+    }
+    // Partition the boundary arcs into those along the rim of a face and those
+    // of edges that are not part of any face.
+    let mut is_faceless = false;
+    let mut boundaries = HashSet::<ArcKey>::new();
+    for ab in arcs.iter().cloned() {
+        let arc = graph.arc(ab).expect_consistent();
+        if arc.is_boundary_arc() {
+            if arc.opposite_arc().is_boundary_arc() {
+                is_faceless = true;
+            }
+            else {
+                boundaries.insert(ab);
+            }
+        }
+    }
+    // Count the loops formed by boundary arcs. Loops are followed through
+    // their vertices rather than rings, because the rings of boundary arcs may
+    // include arcs of edges that are not part of any face.
+    let mut boundary_count = 0;
+    while let Some(ab) = boundaries.iter().cloned().nth(0) {
+        boundary_count += 1;
+        let mut buffer = vec![ab];
+        while let Some(ab) = buffer.pop() {
+            if boundaries.remove(&ab) {
+                buffer.extend(
+                    graph
+                        .arc(ab)
+                        .expect_consistent()
+                        .destination_vertex()
+                        .outgoing_arcs()
+                        .map(|arc| arc.key())
+                        .filter(|key| boundaries.contains(key)),
+                );
+            }
+        }
+    }
+    let edge_count = arcs.len() / 2;
+    let face_count = faces.len();
+    let characteristic = vertex_count as isize - edge_count as isize + face_count as isize;
+    let genus = 2 - characteristic - boundary_count as isize;
+    let genus = if !is_faceless && genus >= 0 && genus % 2 == 0 {
+        Some((genus / 2) as usize)
+    }
+    else {
+        None
+    };
+    Invariants {
+        vertex_count,
+        edge_count,
+        face_count,
+        boundary_count,
+        component_count: 1,
+        genus,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use decorum::R64;
+    use nalgebra::Point3;
+
+    use crate::graph::{fixture, MeshGraph};
+    use crate::index::HashIndexer;
+    use crate::prelude::*;
+    use crate::primitive::cube::Cube;
+    use crate::primitive::generate::Position;
+    use crate::primitive::sphere::UvSphere;
+    use crate::primitive::Trigon;
+
+    type E3 = Point3<R64>;
+
+    #[test]
+    fn sphere_invariants() {
+        let graph: MeshGraph<E3> = UvSphere::new(8, 8)
+            .polygons::<Position<E3>>()
+            .collect_with_indexer(HashIndexer::default())
+            .unwrap();
+        let invariants = graph.invariants();
+
+        assert_eq!(2, invariants.euler_characteristic());
+        assert_eq!(Some(0), invariants.genus());
+        assert_eq!(0, invariants.boundary_count());
+        assert!(invariants.is_closed());
+    }
+
+    #[test]
+    fn open_cube_invariants() {
+        let mut graph: MeshGraph<E3> = Cube::new()
+            .polygons::<Position<E3>>()
+            .collect_with_indexer(HashIndexer::default())
+            .unwrap();
+        let key = graph.faces().nth(0).unwrap().key();
+        graph.face_mut(key).unwrap().remove();
+        let invariants = graph.invariants();
+
+        // Removing a face from a cube yields a disc.
+        assert_eq!(1, invariants.euler_characteristic());
+        assert_eq!(Some(0), invariants.genus());
+        assert_eq!(1, invariants.boundary_count());
+        assert!(!invariants.is_closed());
+    }
+
+    #[test]
+    fn disjoint_subgraph_invariants() {
+        let graph = MeshGraph::<Point3<f64>>::from_raw_buffers(
+            vec![Trigon::new(0u32, 1, 2), Trigon::new(3, 4, 5)],
+            vec![
+                (0.0, 0.0, 0.0),
+                (1.0, 0.0, 0.0),
+                (0.0, 1.0, 0.0),
+                (0.0, 0.0, 1.0),
+                (1.0, 0.0, 1.0),
+                (0.0, 1.0, 1.0),
+            ],
+        )
+        .unwrap();
+        let invariants = graph.disjoint_subgraph_invariants();
+
+        assert_eq!(2, invariants.len());
+        assert!(invariants
+            .iter()
+            .all(|invariants| invariants.euler_characteristic() == 1));
+        assert_eq!(2, graph.invariants().component_count());
+        assert_eq!(2, graph.invariants().boundary_count());
+    }
+
+    #[test]
+    fn non_orientable_invariants() {
+        // A triangulated Möbius strip. Its winding conflicts along an edge,
+        // which is split when the graph is constructed, so the sub-graphs of
+        // the graph are planar.
+        let (graph, repair) = fixture::mobius();
+        assert_eq!(1, repair.non_orientable_faces().len());
+
+        let invariants = graph.invariants();
+        assert_eq!(Some(0), invariants.genus());
+        assert!(!invariants.is_closed());
+        for invariants in graph.disjoint_subgraph_invariants() {
+            assert_eq!(Some(0), invariants.genus());
+            assert!(invariants.boundary_count() > 0);
+        }
+    }
+
+    #[test]
+    fn dangling_edge_invariants() {
+        let mut graph = MeshGraph::<Point3<f64>>::from_raw_buffers(
+            vec![Trigon::new(0usize, 1, 2), Trigon::new(2, 1, 3)],
+            vec![
+                (0.0, 0.0, 0.0),
+                (1.0, 0.0, 0.0),
+                (0.0, 1.0, 0.0),
+                (1.0, 1.0, 0.0),
+            ],
+        )
+        .unwrap();
+        // Removing a face does not remove its arcs, so the edges that are not
+        // shared with the remaining face are left dangling.
+        let key = graph
+            .faces()
+            .find(|face| {
+                face.adjacent_vertices()
+                    .any(|vertex| vertex.position().x + vertex.position().y > 1.5)
+            })
+            .unwrap()
+            .key();
+        graph.face_mut(key).unwrap().remove();
+        let invariants = graph.invariants();
+
+        assert_eq!(5, invariants.edge_count());
+        assert_eq!(1, invariants.face_count());
+        assert_eq!(None, invariants.genus());
+        // The dangling edges do not form a boundary loop.
+        assert_eq!(1, invariants.boundary_count());
+    }
+}
@@ -0,0 +1,562 @@
+//! Validation of graph consistency.
+//!
+//! Validation inspects the storage of a graph directly rather than using views,
+//! because views assume that a graph is consistent and may panic if it is not.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::entity::storage::prelude::*;
+use crate::entity::storage::AsStorageOf;
+use crate::graph::data::GraphData;
+use crate::graph::edge::{Arc, ArcKey, Edge, EdgeKey};
+use crate::graph::face::{Face, FaceKey};
+use crate::graph::vertex::{Vertex, VertexKey};
+use crate::graph::{GraphKey, MeshGraph};
+
+/// Violation of a consistency invariant in a [`MeshGraph`].
+///
+/// Each defect names the key of the offending entity. Note that a single fault
+/// typically manifests as more than one defect, because entities refer to one
+/// another. For example, an arc that refers to a missing face is reported as
+/// such and the ring of that face (if any) is also reported as malformed.
+///
+/// See [`MeshGraph::validate`].
+///
+/// [`MeshGraph`]: crate::graph::MeshGraph
+/// [`MeshGraph::validate`]: crate::graph::MeshGraph::validate
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Defect {
+    /// A vertex with outgoing arcs has no leading arc or its leading arc does
+    /// not exist.
+    ///
+    /// Isolated vertices with no outgoing arcs and no leading arc are not
+    /// defects.
+    VertexArcMissing(VertexKey),
+    /// The leading arc of a vertex does not originate at that vertex.
+    VertexArcInconsistent(VertexKey),
+    /// A vertex is non-manifold.
+    ///
+    /// The neighborhood of the vertex is not a single disc or half-disc, such
+    /// as a vertex that pinches two or more fans of faces together.
+    VertexNonManifold(VertexKey),
+    /// The opposite of an arc does not exist.
+    ArcOppositeMissing(ArcKey),
+    /// An arc has no next arc or its next arc does not exist.
+    ArcNextMissing(ArcKey),
+    /// An arc has no previous arc or its previous arc does not exist.
+    ArcPreviousMissing(ArcKey),
+    /// The next arc of an arc does not refer back to that arc as its previous
+    /// arc or vice versa.
+    ArcAdjacencyInconsistent(ArcKey),
+    /// An arc and its next arc are not oriented consistently: the next arc does
+    /// not originate at the destination vertex of the arc.
+    ArcOrientationInconsistent(ArcKey),
+    /// An arc has no edge or its edge does not exist.
+    ArcEdgeMissing(ArcKey),
+    /// An arc and its opposite arc refer to different edges or their edge does
+    /// not refer to either arc.
+    ArcEdgeInconsistent(ArcKey),
+    /// An arc refers to a face that does not exist.
+    ArcFaceMissing(ArcKey),
+    /// The leading arc of an edge does not exist.
+    EdgeArcMissing(EdgeKey),
+    /// Neither arc of an edge is part of a face.
+    EdgeDangling(EdgeKey),
+    /// The leading arc of a face does not exist.
+    FaceArcMissing(FaceKey),
+    /// The ring of a face does not close or includes arcs that do not refer to
+    /// the face.
+    FaceRingMalformed(FaceKey),
+    /// A face has fewer than three vertices or visits a vertex more than once.
+    FaceDegenerate(FaceKey),
+}
+
+impl Defect {
+    /// Gets the key of the offending entity.
+    pub fn key(&self) -> GraphKey {
+        match *self {
+            Defect::VertexArcMissing(key)
+            | Defect::VertexArcInconsistent(key)
+            | Defect::VertexNonManifold(key) => key.into(),
+            Defect::ArcOppositeMissing(key)
+            | Defect::ArcNextMissing(key)
+            | Defect::ArcPreviousMissing(key)
+            | Defect::ArcAdjacencyInconsistent(key)
+            | Defect::ArcOrientationInconsistent(key)
+            | Defect::ArcEdgeMissing(key)
+            | Defect::ArcEdgeInconsistent(key)
+            | Defect::ArcFaceMissing(key) => key.into(),
+            Defect::EdgeArcMissing(key) | Defect::EdgeDangling(key) => key.into(),
+            Defect::FaceArcMissing(key)
+            | Defect::FaceRingMalformed(key)
+            | Defect::FaceDegenerate(key) => key.into(),
+        }
+    }
+}
+
+/// Report of the [`Defect`]s found in a [`MeshGraph`].
+///
+/// See [`MeshGraph::validate`].
+///
+/// [`Defect`]: crate::graph::Defect
+/// [`MeshGraph`]: crate::graph::MeshGraph
+/// [`MeshGraph::validate`]: crate::graph::MeshGraph::validate
+#[derive(Clone, Debug, Default)]
+pub struct ValidationReport {
+    defects: Vec<Defect>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if no defects were found.
+    pub fn is_valid(&self) -> bool {
+        self.defects.is_empty()
+    }
+
+    /// Gets the defects that were found.
+    pub fn defects(&self) -> &[Defect] {
+        self.defects.as_slice()
+    }
+
+    /// Gets the keys of all offending entities.
+    ///
+    /// An entity may be named by more than one defect, but its key is only
+    /// returned once.
+    pub fn keys(&self) -> HashSet<GraphKey> {
+        self.defects.iter().map(|defect| defect.key()).collect()
+    }
+
+    /// Converts the report into the defects that were found.
+    pub fn into_defects(self) -> Vec<Defect> {
+        self.defects
+    }
+
+    fn push(&mut self, defect: Defect) {
+        self.defects.push(defect);
+    }
+}
+
+impl IntoIterator for ValidationReport {
+    type Item = Defect;
+    type IntoIter = std::vec::IntoIter<Defect>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.defects.into_iter()
+    }
+}
+
+pub fn validate<G>(graph: &MeshGraph<G>) -> ValidationReport
+where
+    G: GraphData,
+{
+    let mut report = ValidationReport::default();
+    validate_vertices(graph, &mut report);
+    validate_arcs(graph, &mut report);
+    validate_edges(graph, &mut report);
+    validate_faces(graph, &mut report);
+    report
+}
+
+fn validate_vertices<G>(graph: &MeshGraph<G>, report: &mut ValidationReport)
+where
+    G: GraphData,
+{
+    let vertices = graph.core.as_storage_of::<Vertex<_>>();
+    let arcs = graph.core.as_storage_of::<Arc<_>>();
+    let mut outgoing = HashMap::<VertexKey, Vec<ArcKey>>::with_capacity(vertices.len());
+    for (ab, _) in arcs.iter() {
+        let (a, _) = ab.into();
+        outgoing.entry(a).or_insert_with(Vec::new).push(ab);
+    }
+    for (a, vertex) in vertices.iter() {
+        let outgoing = outgoing.get(&a).map(|keys| keys.as_slice()).unwrap_or(&[]);
+        let ab = match vertex.arc {
+            Some(ab) if arcs.contains_key(&ab) => ab,
+            // Isolated vertices have no leading arc.
+            None if outgoing.is_empty() => continue,
+            _ => {
+                report.push(Defect::VertexArcMissing(a));
+                continue;
+            }
+        };
+        let (source, _) = ab.into();
+        if source != a {
+            report.push(Defect::VertexArcInconsistent(a));
+            continue;
+        }
+        // More than one outgoing boundary arc indicates that the vertex joins
+        // more than one fan of faces.
+        let boundaries = outgoing
+            .iter()
+            .flat_map(|ax| arcs.get(ax))
+            .filter(|arc| arc.face.is_none())
+            .count();
+        // Circulate about the vertex from its leading arc. In a manifold
+        // neighborhood, this reaches every outgoing arc exactly once.
+        let mut circulated = HashSet::with_capacity(outgoing.len());
+        let mut ax = ab;
+        while circulated.len() <= outgoing.len() && circulated.insert(ax) {
+            match arcs
+                .get(&ax.into_opposite())
+                .and_then(|xa| xa.next)
+                .filter(|ay| arcs.contains_key(ay))
+            {
+                Some(ay) if ay != ab => {
+                    ax = ay;
+                }
+                _ => break,
+            }
+        }
+        if boundaries > 1 || circulated.len() != outgoing.len() {
+            report.push(Defect::VertexNonManifold(a));
+        }
+    }
+}
+
+fn validate_arcs<G>(graph: &MeshGraph<G>, report: &mut ValidationReport)
+where
+    G: GraphData,
+{
+    let arcs = graph.core.as_storage_of::<Arc<_>>();
+    let edges = graph.core.as_storage_of::<Edge<_>>();
+    let faces = graph.core.as_storage_of::<Face<_>>();
+    for (ab, arc) in arcs.iter() {
+        let (_, b) = ab.into();
+        let ba = ab.into_opposite();
+        let opposite = arcs.get(&ba);
+        if opposite.is_none() {
+            report.push(Defect::ArcOppositeMissing(ab));
+        }
+        match arc.next.and_then(|bx| arcs.get(&bx).map(|next| (bx, next))) {
+            Some((bx, next)) => {
+                if next.previous != Some(ab) {
+                    report.push(Defect::ArcAdjacencyInconsistent(ab));
+                }
+                let (source, _) = bx.into();
+                if source != b {
+                    report.push(Defect::ArcOrientationInconsistent(ab));
+                }
+            }
+            None => {
+                report.push(Defect::ArcNextMissing(ab));
+            }
+        }
+        match arc.previous.and_then(|xa| arcs.get(&xa)) {
+            Some(previous) => {
+                if previous.next != Some(ab) {
+                    report.push(Defect::ArcAdjacencyInconsistent(ab));
+                }
+            }
+            None => {
+                report.push(Defect::ArcPreviousMissing(ab));
+            }
+        }
+        match arc
+            .edge
+            .and_then(|key| edges.get(&key).map(|edge| (key, edge)))
+        {
+            Some((key, edge)) => {
+                if !(edge.arc == ab || edge.arc == ba)
+                    || opposite.map_or(false, |opposite| opposite.edge != Some(key))
+                {
+                    report.push(Defect::ArcEdgeInconsistent(ab));
+                }
+            }
+            None => {
+                report.push(Defect::ArcEdgeMissing(ab));
+            }
+        }
+        if let Some(key) = arc.face {
+            if !faces.contains_key(&key) {
+                report.push(Defect::ArcFaceMissing(ab));
+            }
+        }
+    }
+}
+
+fn validate_edges<G>(graph: &MeshGraph<G>, report: &mut ValidationReport)
+where
+    G: GraphData,
+{
+    let arcs = graph.core.as_storage_of::<Arc<_>>();
+    let edges = graph.core.as_storage_of::<Edge<_>>();
+    for (key, edge) in edges.iter() {
+        let ab = edge.arc;
+        match arcs.get(&ab) {
+            Some(arc) => {
+                let is_dangling = arc.face.is_none()
+                    && arcs
+                        .get(&ab.into_opposite())
+                        .map_or(true, |opposite| opposite.face.is_none());
+                if is_dangling {
+                    report.push(Defect::EdgeDangling(key));
+                }
+            }
+            None => {
+                report.push(Defect::EdgeArcMissing(key));
+            }
+        }
+    }
+}
+
+fn validate_faces<G>(graph: &MeshGraph<G>, report: &mut ValidationReport)
+where
+    G: GraphData,
+{
+    let arcs = graph.core.as_storage_of::<Arc<_>>();
+    let faces = graph.core.as_storage_of::<Face<_>>();
+    for (key, face) in faces.iter() {
+        if !arcs.contains_key(&face.arc) {
+            report.push(Defect::FaceArcMissing(key));
+            continue;
+        }
+        // Walk the ring of the face. The walk is bounded by the number of arcs
+        // in the graph, so a ring that does not close is always detected.
+        let mut vertices = HashSet::new();
+        let mut arity = 0usize;
+        let mut is_degenerate = false;
+        let mut is_malformed = true;
+        let mut ab = face.arc;
+        while arity <= arcs.len() {
+            let arc = match arcs.get(&ab).filter(|arc| arc.face == Some(key)) {
+                Some(arc) => arc,
+                None => break,
+            };
+            let (a, _) = ab.into();
+            if !vertices.insert(a) {
+                is_degenerate = true;
+            }
+            arity += 1;
+            match arc.next {
+                Some(next) if next == face.arc => {
+                    is_malformed = false;
+                    break;
+                }
+                Some(next) => {
+                    ab = next;
+                }
+                None => break,
+            }
+        }
+        if is_malformed {
+            report.push(Defect::FaceRingMalformed(key));
+        }
+        else if is_degenerate || arity < 3 {
+            report.push(Defect::FaceDegenerate(key));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use decorum::R64;
+    use nalgebra::Point3;
+    use std::collections::HashSet;
+
+    use crate::entity::storage::prelude::*;
+    use crate::entity::storage::AsStorageMut;
+    use crate::graph::edge::{Arc, ArcKey, Edge};
+    use crate::graph::face::Face;
+    use crate::graph::vertex::{Vertex, VertexKey};
+    use crate::graph::{Defect, GraphKey, MeshGraph};
+    use crate::index::HashIndexer;
+    use crate::prelude::*;
+    use crate::primitive::cube::Cube;
+    use crate::primitive::generate::Position;
+    use crate::primitive::sphere::UvSphere;
+    use crate::primitive::Trigon;
+
+    type E3 = Point3<R64>;
+
+    fn vertex(graph: &MeshGraph<Point3<i32>>, x: i32, y: i32) -> VertexKey {
+        graph
+            .vertices()
+            .find(|vertex| vertex.data == Point3::new(x, y, 0))
+            .unwrap()
+            .key()
+    }
+
+    fn assert_defects(expected: &[Defect], defects: &[Defect]) {
+        assert_eq!(expected.len(), defects.len());
+        assert_eq!(
+            expected.iter().cloned().collect::<HashSet<_>>(),
+            defects.iter().cloned().collect::<HashSet<_>>(),
+        );
+    }
+
+    #[test]
+    fn validate_consistent_graph() {
+        let graph: MeshGraph<E3> = UvSphere::new(8, 8)
+            .polygons::<Position<E3>>()
+            .collect_with_indexer(HashIndexer::default())
+            .unwrap();
+
+        assert!(graph.validate().is_valid());
+    }
+
+    #[test]
+    fn validate_missing_leading_arc() {
+        let mut graph: MeshGraph<E3> = Cube::new()
+            .polygons::<Position<E3>>()
+            .collect_with_indexer(HashIndexer::default())
+            .unwrap();
+        let key = graph.vertices().nth(0).unwrap().key();
+        AsStorageMut::<Vertex<_>>::as_storage_mut(&mut graph)
+            .get_mut(&key)
+            .unwrap()
+            .arc = None;
+
+        let report = graph.validate();
+        assert_eq!(&[Defect::VertexArcMissing(key)], report.defects());
+    }
+
+    #[test]
+    fn validate_isolated_vertex() {
+        let mut graph: MeshGraph<E3> = Cube::new()
+            .polygons::<Position<E3>>()
+            .collect_with_indexer(HashIndexer::default())
+            .unwrap();
+        AsStorageMut::<Vertex<_>>::as_storage_mut(&mut graph).insert(Vertex::new(E3::origin()));
+
+        assert!(graph.validate().is_valid());
+    }
+
+    #[test]
+    fn validate_non_manifold_vertex() {
+        // Two triangles that share only the vertex at the origin.
+        let graph = MeshGraph::<Point3<i32>>::from_raw_buffers(
+            vec![Trigon::new(0usize, 1, 2), Trigon::new(0, 3, 4)],
+            vec![(0, 0, 0), (1, 0, 0), (1, 1, 0), (-1, 0, 0), (-1, -1, 0)],
+        )
+        .unwrap();
+
+        let report = graph.validate();
+        assert_eq!(
+            &[Defect::VertexNonManifold(vertex(&graph, 0, 0))],
+            report.defects()
+        );
+    }
+
+    #[test]
+    fn validate_dangling_edges() {
+        let mut graph = MeshGraph::<Point3<i32>>::from_raw_buffers(
+            vec![Trigon::new(0usize, 1, 2), Trigon::new(2, 1, 3)],
+            vec![(0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 1, 0)],
+        )
+        .unwrap();
+        let (a, b, c) = (
+            vertex(&graph, 1, 0),
+            vertex(&graph, 0, 1),
+            vertex(&graph, 1, 1),
+        );
+        let ac = graph.arc((a, c).into()).unwrap().edge().key();
+        let cb = graph.arc((c, b).into()).unwrap().edge().key();
+        // Removing a face does not remove its arcs, so the edges that are not
+        // shared with the remaining face are left dangling. The vertices of
+        // the removed face are then joined by more than one boundary.
+        let key = graph.arc((a, c).into()).unwrap().face().unwrap().key();
+        graph.face_mut(key).unwrap().remove();
+
+        let report = graph.validate();
+        assert_defects(
+            &[
+                Defect::EdgeDangling(ac),
+                Defect::EdgeDangling(cb),
+                Defect::VertexNonManifold(a),
+                Defect::VertexNonManifold(b),
+                Defect::VertexNonManifold(c),
+            ],
+            report.defects(),
+        );
+    }
+
+    #[test]
+    fn validate_inconsistent_orientation() {
+        let mut graph = MeshGraph::<Point3<i32>>::from_raw_buffers(
+            vec![Trigon::new(0usize, 1, 2)],
+            vec![(0, 0, 0), (1, 0, 0), (0, 1, 0)],
+        )
+        .unwrap();
+        let (a, b, c) = (
+            vertex(&graph, 0, 0),
+            vertex(&graph, 1, 0),
+            vertex(&graph, 0, 1),
+        );
+        let (ab, bc, ca): (ArcKey, ArcKey, ArcKey) = ((a, b).into(), (b, c).into(), (c, a).into());
+        // Reverse the ring of the face without reversing its arcs. Adjacency
+        // remains symmetric, but each arc is followed by an arc that does not
+        // originate at its destination.
+        for (key, next, previous) in [(ab, ca, bc), (bc, ab, ca), (ca, bc, ab)].iter().cloned() {
+            let arc = AsStorageMut::<Arc<_>>::as_storage_mut(&mut graph)
+                .get_mut(&key)
+                .unwrap();
+            arc.next = Some(next);
+            arc.previous = Some(previous);
+        }
+
+        let report = graph.validate();
+        assert_defects(
+            &[
+                Defect::ArcOrientationInconsistent(ab),
+                Defect::ArcOrientationInconsistent(bc),
+                Defect::ArcOrientationInconsistent(ca),
+                // Circulating about the vertices no longer visits their
+                // outgoing arcs exactly once.
+                Defect::VertexNonManifold(a),
+                Defect::VertexNonManifold(b),
+                Defect::VertexNonManifold(c),
+            ],
+            report.defects(),
+        );
+    }
+
+    #[test]
+    fn validate_degenerate_face() {
+        // Construct a face with only two vertices directly in storage.
+        let mut graph = MeshGraph::<Point3<i32>>::new();
+        let a = AsStorageMut::<Vertex<_>>::as_storage_mut(&mut graph)
+            .insert(Vertex::new(Point3::new(0, 0, 0)));
+        let b = AsStorageMut::<Vertex<_>>::as_storage_mut(&mut graph)
+            .insert(Vertex::new(Point3::new(1, 0, 0)));
+        let (ab, ba): (ArcKey, ArcKey) = ((a, b).into(), (b, a).into());
+        let edge = AsStorageMut::<Edge<_>>::as_storage_mut(&mut graph).insert(Edge::new(ab, ()));
+        let face = AsStorageMut::<Face<_>>::as_storage_mut(&mut graph).insert(Face::new(ab, ()));
+        for (key, opposite) in [(ab, ba), (ba, ab)].iter().cloned() {
+            let mut arc = Arc::new(());
+            arc.next = Some(opposite);
+            arc.previous = Some(opposite);
+            arc.edge = Some(edge);
+            arc.face = Some(face);
+            AsStorageMut::<Arc<_>>::as_storage_mut(&mut graph).insert_with_key(&key, arc);
+        }
+        for (key, arc) in [(a, ab), (b, ba)].iter().cloned() {
+            AsStorageMut::<Vertex<_>>::as_storage_mut(&mut graph)
+                .get_mut(&key)
+                .unwrap()
+                .arc = Some(arc);
+        }
+
+        let report = graph.validate();
+        assert_eq!(&[Defect::FaceDegenerate(face)], report.defects());
+    }
+
+    #[test]
+    fn validate_malformed_ring() {
+        let mut graph: MeshGraph<E3> = Cube::new()
+            .polygons::<Position<E3>>()
+            .collect_with_indexer(HashIndexer::default())
+            .unwrap();
+        let key = graph.faces().nth(0).unwrap().key();
+        let abc = graph.faces().nth(1).unwrap().key();
+        // Point one face at the leading arc of another.
+        let arc = graph.face(abc).unwrap().arc().key();
+        AsStorageMut::<Face<_>>::as_storage_mut(&mut graph)
+            .get_mut(&key)
+            .unwrap()
+            .arc = arc;
+
+        let report = graph.validate();
+        assert!(!report.is_valid());
+        assert!(report.keys().contains(&GraphKey::Face(key)));
+        assert!(!report.keys().contains(&GraphKey::Face(abc)));
+    }
+}
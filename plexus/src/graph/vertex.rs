@@ -5,7 +5,7 @@ use std::borrow::Borrow;
 use std::hash::{Hash, Hasher};
 use std::mem;
 use std::ops::{Deref, DerefMut};
-use theon::space::Vector;
+use theon::space::{EuclideanSpace, Scalar, Vector};
 use theon::AsPosition;
 
 use crate::entity::borrow::{Reborrow, ReborrowInto, ReborrowMut};
@@ -21,7 +21,10 @@ use crate::geometry::Metric;
 use crate::graph::data::{Data, GraphData, Parametric};
 use crate::graph::edge::{Arc, ArcKey, ArcOrphan, ArcView, Edge};
 use crate::graph::face::{Face, FaceKey, FaceOrphan, FaceView};
-use crate::graph::geometry::{VertexCentroid, VertexNormal, VertexPosition};
+use crate::graph::geometry::{
+    Curvature, FaceArea, NormalWeight, VertexCentroid, VertexCurvature, VertexNormal,
+    VertexPosition,
+};
 use crate::graph::mutation::vertex::{self, VertexRemoveCache};
 use crate::graph::mutation::{self, Consistent, Immediate, Mutable};
 use crate::graph::path::Path;
@@ -253,15 +256,88 @@ where
     }
 
     pub fn into_shortest_path_with<Q, F>(
+        self,
+        key: VertexKey,
+        f: F,
+    ) -> Result<Path<'static, B>, GraphError>
+    where
+        Q: Copy + Metric,
+        F: Fn(VertexView<&M>, VertexView<&M>) -> Q,
+    {
+        self.into_shortest_path_with_heuristic(key, f, |_| Q::zero())
+    }
+
+    /// Gets the shortest path to the given vertex using A* search.
+    ///
+    /// The heuristic estimates the metric from a vertex to the destination
+    /// vertex and must never overestimate it. For example, if the metric is
+    /// the Euclidean distance between vertices, then the Euclidean distance to
+    /// the destination vertex is an appropriate heuristic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the destination vertex is not found or is
+    /// unreachable or if the metric is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate decorum;
+    /// # extern crate nalgebra;
+    /// # extern crate plexus;
+    /// # extern crate theon;
+    /// #
+    /// use decorum::R64;
+    /// use nalgebra::Point3;
+    /// use plexus::graph::MeshGraph;
+    /// use plexus::prelude::*;
+    /// use plexus::primitive::generate::Position;
+    /// use plexus::primitive::sphere::UvSphere;
+    /// use theon::space::InnerSpace;
+    ///
+    /// type E3 = Point3<R64>;
+    ///
+    /// let graph: MeshGraph<E3> = UvSphere::new(16, 8).polygons::<Position<E3>>().collect();
+    /// let from = graph.vertices().nth(0).unwrap();
+    /// let to = graph.vertices().nth(1).unwrap();
+    /// let position = *to.position();
+    /// let path = from
+    ///     .shortest_path_with_heuristic(
+    ///         to.key(),
+    ///         |from, to| (*to.position() - *from.position()).magnitude(),
+    ///         |vertex| (position - *vertex.position()).magnitude(),
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn shortest_path_with_heuristic<Q, F, H>(
+        &self,
+        key: VertexKey,
+        f: F,
+        h: H,
+    ) -> Result<Path<'static, &M>, GraphError>
+    where
+        Q: Copy + Metric,
+        F: Fn(VertexView<&M>, VertexView<&M>) -> Q,
+        H: Fn(VertexView<&M>) -> Q,
+    {
+        self.to_ref().into_shortest_path_with_heuristic(key, f, h)
+    }
+
+    pub fn into_shortest_path_with_heuristic<Q, F, H>(
         self,
         mut key: VertexKey,
         f: F,
+        h: H,
     ) -> Result<Path<'static, B>, GraphError>
     where
         Q: Copy + Metric,
         F: Fn(VertexView<&M>, VertexView<&M>) -> Q,
+        H: Fn(VertexView<&M>) -> Q,
     {
-        let metrics = dijkstra::metrics_with(self.to_ref(), Some(key), f)?;
+        let metrics = {
+            let (storage, from) = self.to_ref().unbind();
+            dijkstra::search_with(storage, Some(from), Some(key), f, h)?
+        };
         let mut keys = vec![key];
         while let Some((Some(previous), _)) = metrics.get(&key) {
             key = *previous;
@@ -309,6 +385,64 @@ where
     {
         <G as VertexNormal>::normal(self.to_ref())
     }
+
+    /// Gets the normal of the vertex using the given weighting of the normals
+    /// of its adjacent faces.
+    ///
+    /// See [`NormalWeight`].
+    ///
+    /// [`NormalWeight`]: crate::graph::NormalWeight
+    pub fn normal_with(&self, weight: NormalWeight) -> Result<Vector<VertexPosition<G>>, GraphError>
+    where
+        G: FaceArea + VertexNormal,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+    {
+        <G as VertexNormal>::weighted_normal(self.to_ref(), weight)
+    }
+
+    /// Gets the Gaussian curvature at the vertex.
+    ///
+    /// See [`VertexCurvature::gaussian_curvature`].
+    ///
+    /// [`VertexCurvature::gaussian_curvature`]: crate::graph::VertexCurvature::gaussian_curvature
+    pub fn gaussian_curvature(&self) -> Result<Scalar<VertexPosition<G>>, GraphError>
+    where
+        G: VertexCurvature,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+    {
+        <G as VertexCurvature>::gaussian_curvature(self.to_ref())
+    }
+
+    /// Gets the mean curvature at the vertex.
+    ///
+    /// See [`VertexCurvature::mean_curvature`].
+    ///
+    /// [`VertexCurvature::mean_curvature`]: crate::graph::VertexCurvature::mean_curvature
+    pub fn mean_curvature(&self) -> Result<Scalar<VertexPosition<G>>, GraphError>
+    where
+        G: VertexCurvature,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+    {
+        <G as VertexCurvature>::mean_curvature(self.to_ref())
+    }
+
+    /// Gets the Gaussian, mean, and principal curvatures and the principal
+    /// directions at the vertex.
+    ///
+    /// See [`VertexCurvature::curvature`].
+    ///
+    /// [`VertexCurvature::curvature`]: crate::graph::VertexCurvature::curvature
+    pub fn curvature(&self) -> Result<Curvature<VertexPosition<G>>, GraphError>
+    where
+        G: VertexCurvature,
+        G::Vertex: AsPosition,
+        VertexPosition<G>: EuclideanSpace,
+    {
+        <G as VertexCurvature>::curvature(self.to_ref())
+    }
 }
 
 /// Reachable API.
@@ -1115,7 +1249,7 @@ mod tests {
     use decorum::R64;
     use nalgebra::{Point2, Point3};
 
-    use crate::graph::{GraphError, MeshGraph};
+    use crate::graph::{fixture, GraphError, MeshGraph, NormalWeight};
     use crate::prelude::*;
     use crate::primitive::cube::Cube;
     use crate::primitive::generate::Position;
@@ -1197,4 +1331,87 @@ mod tests {
         let vertex = graph.vertices().nth(0).unwrap();
         assert_eq!(graph.vertex_count(), vertex.traverse_by_depth().count());
     }
+
+    #[test]
+    fn planar_curvature() {
+        let mut graph = fixture::grid();
+        graph.triangulate();
+        let vertex = graph
+            .vertices()
+            .find(|vertex| *vertex.position() == Point3::new(1.0, 1.0, 0.0))
+            .unwrap();
+        let curvature = vertex.curvature().unwrap();
+
+        assert!(curvature.gaussian.abs() < 1e-6);
+        assert!(curvature.mean.abs() < 1e-6);
+        assert!(curvature.maximum.abs() < 1e-6);
+        assert!(curvature.minimum.abs() < 1e-6);
+    }
+
+    #[test]
+    fn spherical_curvature() {
+        let mut graph: MeshGraph<E3> = UvSphere::new(32, 16).polygons::<Position<E3>>().collect();
+        graph.triangulate();
+
+        // The curvatures of a unit sphere are one everywhere. Away from the
+        // poles, the discrete estimates approximate this. The faces of the
+        // sphere are oriented outward, so its mean curvature is positive.
+        let vertex = graph
+            .vertices()
+            .find(|vertex| vertex.position().z.abs() < 0.1.into())
+            .unwrap();
+        let curvature = vertex.curvature().unwrap();
+        assert!((curvature.gaussian.into_inner() - 1.0).abs() < 0.1);
+        assert!((curvature.mean.into_inner() - 1.0).abs() < 0.1);
+        assert_eq!(
+            graph.vertex_count(),
+            graph.vertex_curvatures().unwrap().len()
+        );
+    }
+
+    #[test]
+    fn weighted_normals() {
+        // A large triangle in the xy-plane and a sliver triangle in the
+        // xz-plane that share an edge. The corners of both triangles at the
+        // origin are right angles.
+        let graph = MeshGraph::<Point3<f64>>::from_raw_buffers(
+            vec![Trigon::new(0usize, 1, 2), Trigon::new(1, 0, 3)],
+            vec![
+                (0.0, 0.0, 0.0),
+                (1.0, 0.0, 0.0),
+                (0.0, 10.0, 0.0),
+                (0.0, 0.0, -0.1),
+            ],
+        )
+        .unwrap();
+        let vertex = graph
+            .vertices()
+            .find(|vertex| *vertex.position() == Point3::origin())
+            .unwrap();
+
+        // The face normals at the origin are +Z (large triangle) and -Y
+        // (sliver triangle). Each weighting is given as the unnormalized Y
+        // and Z components of the expected normal. The large triangle
+        // dominates the area-weighted normal while the sliver triangle, with
+        // its short edges, dominates the Max-weighted normal.
+        for (weight, (y, z)) in [
+            (NormalWeight::Uniform, (-1.0, 1.0)),
+            (NormalWeight::Angle, (-1.0, 1.0)),
+            (NormalWeight::Area, (-0.05, 5.0)),
+            (NormalWeight::Max, (-10.0, 0.1)),
+        ]
+        .iter()
+        .cloned()
+        {
+            let magnitude = ((y * y) + (z * z)).sqrt();
+            let normal = vertex.normal_with(weight).unwrap();
+            assert!(normal.x.abs() < 1e-6);
+            assert!((normal.y - (y / magnitude)).abs() < 1e-6);
+            assert!((normal.z - (z / magnitude)).abs() < 1e-6);
+
+            let normals = graph.vertex_normals(weight).unwrap();
+            assert_eq!(4, normals.len());
+            assert!((normals[&vertex.key()] - normal).norm() < 1e-6);
+        }
+    }
 }
@@ -0,0 +1,133 @@
+//! Generalized winding numbers.
+//!
+//! The winding number of a point with respect to a surface is the sum of the
+//! signed solid angles subtended by its faces divided by $4\pi$ (Jacobson et
+//! al.). For closed and consistently oriented surfaces with outward facing
+//! normals, the winding number is one inside and zero outside. For open or
+//! self-intersecting surfaces, the winding number varies smoothly and
+//! thresholding it at one half gives a robust classification.
+//!
+//! Faces are treated as triangulations of their rings.
+
+use decorum::Real;
+use num::{One, Zero};
+use smallvec::SmallVec;
+use theon::ops::{Cross, Dot};
+use theon::space::{EuclideanSpace, FiniteDimensional, InnerSpace, Scalar, Vector};
+use theon::AsPosition;
+use typenum::U3;
+
+use crate::graph::data::GraphData;
+use crate::graph::geometry::{self, VertexPosition};
+use crate::graph::MeshGraph;
+
+/// Gets the triangles of the triangulations of the faces of a graph.
+pub(in crate::graph) fn triangles<G>(graph: &MeshGraph<G>) -> Vec<[VertexPosition<G>; 3]>
+where
+    G: GraphData,
+    G::Vertex: AsPosition,
+    VertexPosition<G>: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<VertexPosition<G>>: Cross<Output = Vector<VertexPosition<G>>>,
+{
+    let mut triangles = Vec::with_capacity(graph.arc_count());
+    for face in graph.faces() {
+        let ring = face
+            .adjacent_vertices()
+            .map(|vertex| *vertex.position())
+            .collect::<SmallVec<[_; 8]>>();
+        triangles.extend(
+            geometry::triangulate(&ring)
+                .into_iter()
+                .map(|[a, b, c]| [ring[a], ring[b], ring[c]]),
+        );
+    }
+    triangles
+}
+
+/// Computes the winding number of a point with respect to a set of
+/// triangles.
+pub(in crate::graph) fn winding_number<S>(triangles: &[[S; 3]], point: S) -> Scalar<S>
+where
+    S: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<S>: Cross<Output = Vector<S>>,
+{
+    let two = Scalar::<S>::one() + One::one();
+    let sum = triangles.iter().fold(Scalar::<S>::zero(), |sum, triangle| {
+        sum + solid_angle(triangle, point)
+    });
+    sum / (two * two * <Scalar<S> as Real>::PI)
+}
+
+/// Returns `true` if a winding number classifies a point as inside of a
+/// surface.
+pub(in crate::graph) fn is_inside<T>(winding: T) -> bool
+where
+    T: Real,
+{
+    winding + winding > T::one()
+}
+
+/// Computes the signed solid angle subtended by a triangle at a point.
+///
+/// This is the formula given by Van Oosterom and Strackee.
+fn solid_angle<S>(triangle: &[S; 3], point: S) -> Scalar<S>
+where
+    S: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<S>: Cross<Output = Vector<S>>,
+{
+    let two = Scalar::<S>::one() + One::one();
+    let [a, b, c] = *triangle;
+    let (a, b, c) = (a - point, b - point, c - point);
+    let (la, lb, lc) = (a.magnitude(), b.magnitude(), c.magnitude());
+    let numerator = a.dot(b.cross(c));
+    let denominator = (la * lb * lc) + (a.dot(b) * lc) + (a.dot(c) * lb) + (b.dot(c) * la);
+    if numerator == Zero::zero() && denominator == Zero::zero() {
+        // The point lies on the triangle.
+        Zero::zero()
+    }
+    else {
+        two * Real::atan2(numerator, denominator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Point3;
+
+    use crate::graph::MeshGraph;
+    use crate::prelude::*;
+    use crate::primitive::cube::Cube;
+    use crate::primitive::generate::Position;
+    use crate::primitive::sphere::UvSphere;
+
+    type E3 = Point3<f64>;
+
+    #[test]
+    fn winding_number() {
+        let graph: MeshGraph<E3> = Cube::new().polygons::<Position<E3>>().collect();
+
+        assert!((graph.winding_number(E3::new(0.0, 0.0, 0.0)) - 1.0).abs() < 1e-9);
+        assert!((graph.winding_number(E3::new(0.25, -0.4, 0.1)) - 1.0).abs() < 1e-9);
+        assert!(graph.winding_number(E3::new(2.0, 0.0, 0.0)).abs() < 1e-9);
+        assert_eq!(
+            vec![true, false, true],
+            graph.contains_points(vec![
+                E3::new(0.1, 0.2, 0.3),
+                E3::new(0.0, 0.0, 0.6),
+                E3::new(-0.4, -0.4, 0.4),
+            ])
+        );
+    }
+
+    #[test]
+    fn contains_point_in_open_graph() {
+        let mut graph: MeshGraph<E3> = UvSphere::new(16, 8).polygons::<Position<E3>>().collect();
+        // Remove a face to open the surface.
+        let key = graph.faces().next().unwrap().key();
+        graph.face_mut(key).unwrap().remove();
+
+        assert!(graph.contains_point(E3::new(0.0, 0.0, 0.0)));
+        assert!(graph.contains_point(E3::new(0.1, 0.2, -0.3)));
+        assert!(!graph.contains_point(E3::new(0.0, 0.0, 2.0)));
+    }
+}
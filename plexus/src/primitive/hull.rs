@@ -0,0 +1,558 @@
+//! Convex hulls.
+//!
+//! This module provides the [`ConvexHull`] of a set of points in three
+//! dimensions, which is computed with the quickhull algorithm (Barber et al.).
+//! Coplanar facets of the hull are merged into polygons of arbitrary arity, so
+//! the hull of the corners of a cube is comprised of six quadrilaterals.
+//!
+//! Points that lie within a small tolerance of the hull are not considered to
+//! be outside of it. This tolerance is relative to the extent of the points.
+//!
+//! # Examples
+//!
+//! Constructing a graph from the convex hull of a set of points:
+//!
+//! ```rust
+//! # extern crate nalgebra;
+//! # extern crate plexus;
+//! #
+//! use nalgebra::Point3;
+//! use plexus::graph::MeshGraph;
+//! use plexus::prelude::*;
+//! use plexus::primitive::hull::ConvexHull;
+//!
+//! type E3 = Point3<f64>;
+//!
+//! let hull = ConvexHull::from_points(vec![
+//!     E3::new(0.0, 0.0, 0.0),
+//!     E3::new(1.0, 0.0, 0.0),
+//!     E3::new(0.0, 1.0, 0.0),
+//!     E3::new(0.0, 0.0, 1.0),
+//!     E3::new(0.1, 0.1, 0.1), // Interior.
+//! ])
+//! .unwrap();
+//! let (indices, positions) = hull.into_raw_buffers();
+//! let graph = MeshGraph::<E3>::from_raw_buffers(indices, positions).unwrap();
+//!
+//! assert_eq!(4, graph.vertex_count());
+//! assert_eq!(4, graph.face_count());
+//! ```
+//!
+//! [`ConvexHull`]: crate::primitive::hull::ConvexHull
+
+use num::{NumCast, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::vec;
+use theon::ops::{Cross, Dot};
+use theon::space::{EuclideanSpace, FiniteDimensional, InnerSpace, Scalar, Vector};
+use typenum::U3;
+
+use crate::geometry::component;
+use crate::primitive::{IntoPolygons, UnboundedPolygon};
+
+/// Tolerance relative to the extent of the points of a hull.
+const TOLERANCE: f64 = 1e-7;
+
+/// Convex hull of a set of points.
+///
+/// The polygons of a hull are oriented such that their normals face outward
+/// and only the points that are vertices of the hull are retained.
+///
+/// See the [module][`hull`] documentation.
+///
+/// [`hull`]: crate::primitive::hull
+#[derive(Clone, Debug)]
+pub struct ConvexHull<S> {
+    indices: Vec<UnboundedPolygon<usize>>,
+    positions: Vec<S>,
+}
+
+impl<S> ConvexHull<S>
+where
+    S: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<S>: Cross<Output = Vector<S>>,
+{
+    /// Computes the convex hull of a set of points.
+    ///
+    /// Returns `None` if the points do not span a volume, such as if there are
+    /// fewer than four points or all of the points are coplanar.
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = S>,
+    {
+        let quickhull = Quickhull::from_points(points.into_iter().collect())?;
+        Some(quickhull.into_hull())
+    }
+}
+
+impl<S> ConvexHull<S> {
+    /// Gets the index buffer of the polygons of the hull.
+    pub fn indices(&self) -> &[UnboundedPolygon<usize>] {
+        self.indices.as_slice()
+    }
+
+    /// Gets the positions of the vertices of the hull.
+    pub fn positions(&self) -> &[S] {
+        self.positions.as_slice()
+    }
+
+    /// Converts the hull into an index buffer and a position buffer.
+    ///
+    /// These buffers can be used to construct a [`MeshGraph`] or
+    /// [`MeshBuffer`] via [`FromRawBuffers`].
+    ///
+    /// [`FromRawBuffers`]: crate::buffer::FromRawBuffers
+    /// [`MeshBuffer`]: crate::buffer::MeshBuffer
+    /// [`MeshGraph`]: crate::graph::MeshGraph
+    pub fn into_raw_buffers(self) -> (Vec<UnboundedPolygon<usize>>, Vec<S>) {
+        let ConvexHull { indices, positions } = self;
+        (indices, positions)
+    }
+}
+
+impl<S> IntoPolygons for ConvexHull<S>
+where
+    S: Clone,
+{
+    type Output = vec::IntoIter<Self::Polygon>;
+    type Polygon = UnboundedPolygon<S>;
+
+    fn into_polygons(self) -> Self::Output {
+        let ConvexHull { indices, positions } = self;
+        indices
+            .into_iter()
+            .map(|polygon| {
+                UnboundedPolygon(
+                    polygon
+                        .into_iter()
+                        .map(|index| positions[index].clone())
+                        .collect(),
+                )
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+/// Triangular facet of a hull under construction.
+struct Facet<S>
+where
+    S: EuclideanSpace,
+{
+    vertices: [usize; 3],
+    normal: Vector<S>,
+    /// Points that are in front of the facet and not yet on the hull.
+    outside: Vec<usize>,
+    is_live: bool,
+}
+
+impl<S> Facet<S>
+where
+    S: EuclideanSpace,
+{
+    fn edges(&self) -> [(usize, usize); 3] {
+        let [a, b, c] = self.vertices;
+        [(a, b), (b, c), (c, a)]
+    }
+}
+
+struct Quickhull<S>
+where
+    S: EuclideanSpace,
+{
+    positions: Vec<S>,
+    facets: Vec<Facet<S>>,
+    /// Map from the directed edges of live facets to those facets.
+    edges: HashMap<(usize, usize), usize>,
+    epsilon: Scalar<S>,
+}
+
+impl<S> Quickhull<S>
+where
+    S: EuclideanSpace + FiniteDimensional<N = U3>,
+    Vector<S>: Cross<Output = Vector<S>>,
+{
+    fn from_points(positions: Vec<S>) -> Option<Self> {
+        if positions.len() < 4 {
+            return None;
+        }
+        // Find the extreme points along each axis and the extent of the
+        // points, from which the tolerance is derived.
+        let mut extremes = [(0usize, 0usize); 3];
+        for (index, position) in positions.iter().enumerate() {
+            let (x, y, z) = position.into_xyz();
+            for (axis, value) in [x, y, z].iter().enumerate() {
+                let (lower, upper) = extremes[axis];
+                if *value < component(positions[lower], axis) {
+                    extremes[axis].0 = index;
+                }
+                if *value > component(positions[upper], axis) {
+                    extremes[axis].1 = index;
+                }
+            }
+        }
+        let extent = S::from_xyz(
+            component(positions[extremes[0].1], 0) - component(positions[extremes[0].0], 0),
+            component(positions[extremes[1].1], 1) - component(positions[extremes[1].0], 1),
+            component(positions[extremes[2].1], 2) - component(positions[extremes[2].0], 2),
+        );
+        let epsilon = (extent - S::origin()).magnitude() * into_scalar::<_, S>(TOLERANCE);
+        if epsilon <= Zero::zero() {
+            return None;
+        }
+
+        // Construct an initial simplex from the most distant pair of extreme
+        // points, the point most distant from the line through them, and the
+        // point most distant from the plane through all three.
+        let (a, b) = extremes
+            .iter()
+            .map(|(lower, upper)| (*lower, *upper))
+            .max_by(|(a0, b0), (a1, b1)| {
+                let d0 = (positions[*b0] - positions[*a0]).magnitude();
+                let d1 = (positions[*b1] - positions[*a1]).magnitude();
+                d0.partial_cmp(&d1).unwrap_or(Ordering::Equal)
+            })?;
+        let ab = positions[b] - positions[a];
+        let (c, distance) = furthest(&positions, |position| {
+            ab.cross(position - positions[a]).magnitude() / ab.magnitude()
+        })?;
+        if distance <= epsilon {
+            return None;
+        }
+        let normal = ab
+            .cross(positions[c] - positions[a])
+            .normalize()
+            .unwrap_or_else(Zero::zero);
+        let (d, _) = furthest(&positions, |position| {
+            let distance = normal.dot(position - positions[a]);
+            if distance < Zero::zero() {
+                Scalar::<S>::zero() - distance
+            }
+            else {
+                distance
+            }
+        })?;
+        let distance = normal.dot(positions[d] - positions[a]);
+        let (b, c) = if distance > epsilon {
+            // The fourth point is in front of the plane, so reverse the
+            // orientation of the base of the simplex.
+            (c, b)
+        }
+        else if distance < Scalar::<S>::zero() - epsilon {
+            (b, c)
+        }
+        else {
+            return None;
+        };
+
+        let mut quickhull = Quickhull {
+            positions,
+            facets: Vec::new(),
+            edges: HashMap::new(),
+            epsilon,
+        };
+        let simplex = [
+            quickhull.insert([a, b, c]),
+            quickhull.insert([a, d, b]),
+            quickhull.insert([b, d, c]),
+            quickhull.insert([c, d, a]),
+        ];
+        let points = (0..quickhull.positions.len())
+            .filter(|index| ![a, b, c, d].contains(index))
+            .collect::<Vec<_>>();
+        quickhull.assign(points, &simplex);
+
+        let mut pending = simplex.to_vec();
+        while let Some(facet) = pending.pop() {
+            if quickhull.facets[facet].is_live && !quickhull.facets[facet].outside.is_empty() {
+                pending.extend(quickhull.expand(facet));
+            }
+        }
+        Some(quickhull)
+    }
+
+    /// Inserts a facet with the given vertices in counter-clockwise order.
+    fn insert(&mut self, vertices: [usize; 3]) -> usize {
+        let [a, b, c] = vertices;
+        let (a, b, c) = (self.positions[a], self.positions[b], self.positions[c]);
+        let index = self.facets.len();
+        let facet = Facet {
+            vertices,
+            normal: (b - a).cross(c - a).normalize().unwrap_or_else(Zero::zero),
+            outside: Vec::new(),
+            is_live: true,
+        };
+        for edge in facet.edges().iter() {
+            self.edges.insert(*edge, index);
+        }
+        self.facets.push(facet);
+        index
+    }
+
+    /// Gets the signed distance from the plane of a facet to a point.
+    fn distance(&self, facet: usize, point: usize) -> Scalar<S> {
+        let facet = &self.facets[facet];
+        facet
+            .normal
+            .dot(self.positions[point] - self.positions[facet.vertices[0]])
+    }
+
+    /// Assigns points to the outside sets of the first facet that they are in
+    /// front of. Points that are not in front of any facet are discarded.
+    fn assign<I>(&mut self, points: I, facets: &[usize])
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        for point in points {
+            if let Some(facet) = facets
+                .iter()
+                .find(|facet| self.distance(**facet, point) > self.epsilon)
+            {
+                self.facets[*facet].outside.push(point);
+            }
+        }
+    }
+
+    /// Expands the hull to include the furthest point in the outside set of a
+    /// facet.
+    ///
+    /// Returns the new facets.
+    fn expand(&mut self, facet: usize) -> Vec<usize> {
+        let eye = self.facets[facet]
+            .outside
+            .iter()
+            .cloned()
+            .max_by(|a, b| {
+                self.distance(facet, *a)
+                    .partial_cmp(&self.distance(facet, *b))
+                    .unwrap_or(Ordering::Equal)
+            })
+            .unwrap();
+        // Find the facets visible from the eye point and the horizon that
+        // bounds them.
+        let mut visible = vec![facet];
+        let mut is_visible = HashSet::new();
+        is_visible.insert(facet);
+        let mut horizon = Vec::new();
+        let mut index = 0;
+        while index < visible.len() {
+            for (a, b) in self.facets[visible[index]].edges().iter().cloned() {
+                let neighbor = match self.edges.get(&(b, a)) {
+                    Some(neighbor) => *neighbor,
+                    None => continue,
+                };
+                if is_visible.contains(&neighbor) {
+                    continue;
+                }
+                if self.distance(neighbor, eye) > self.epsilon {
+                    is_visible.insert(neighbor);
+                    visible.push(neighbor);
+                }
+                else {
+                    horizon.push((a, b));
+                }
+            }
+            index += 1;
+        }
+        // Replace the visible facets with a cone of facets from the horizon to
+        // the eye point.
+        let mut orphans = Vec::new();
+        for facet in visible {
+            for edge in self.facets[facet].edges().iter() {
+                self.edges.remove(edge);
+            }
+            let facet = &mut self.facets[facet];
+            facet.is_live = false;
+            orphans.append(&mut facet.outside);
+        }
+        let facets = horizon
+            .into_iter()
+            .map(|(a, b)| self.insert([a, b, eye]))
+            .collect::<Vec<_>>();
+        orphans.retain(|point| *point != eye);
+        self.assign(orphans, &facets);
+        facets
+    }
+
+    /// Returns `true` if the facet lies in the plane of the given facet.
+    fn is_coplanar(&self, facet: usize, other: usize) -> bool {
+        let zero = Scalar::<S>::zero();
+        self.facets[facet].normal.dot(self.facets[other].normal) > zero
+            && self.facets[other].vertices.iter().all(|point| {
+                let distance = self.distance(facet, *point);
+                distance <= self.epsilon && distance >= zero - self.epsilon
+            })
+    }
+
+    /// Merges coplanar facets into polygons and discards any points that are
+    /// not vertices of the hull.
+    fn into_hull(self) -> ConvexHull<S> {
+        let live = (0..self.facets.len())
+            .filter(|facet| self.facets[*facet].is_live)
+            .collect::<Vec<_>>();
+        // Group connected facets that lie in the plane of the first facet in
+        // the group.
+        let mut groups = HashMap::with_capacity(live.len());
+        let mut members = Vec::new();
+        for seed in live.iter().cloned() {
+            if groups.contains_key(&seed) {
+                continue;
+            }
+            let group = members.len();
+            groups.insert(seed, group);
+            let mut facets = vec![seed];
+            let mut index = 0;
+            while index < facets.len() {
+                for (a, b) in self.facets[facets[index]].edges().iter().cloned() {
+                    if let Some(neighbor) = self.edges.get(&(b, a)) {
+                        if !groups.contains_key(neighbor) && self.is_coplanar(seed, *neighbor) {
+                            groups.insert(*neighbor, group);
+                            facets.push(*neighbor);
+                        }
+                    }
+                }
+                index += 1;
+            }
+            members.push(facets);
+        }
+        let mut polygons: Vec<Vec<usize>> = Vec::with_capacity(members.len());
+        for (group, facets) in members.iter().enumerate() {
+            if facets.len() == 1 {
+                polygons.push(self.facets[facets[0]].vertices.to_vec());
+                continue;
+            }
+            // Walk the edges of the group that are shared with other groups.
+            let boundary = facets
+                .iter()
+                .flat_map(|facet| self.facets[*facet].edges().to_vec())
+                .filter(|(a, b)| {
+                    self.edges
+                        .get(&(*b, *a))
+                        .map_or(true, |neighbor| groups[neighbor] != group)
+                })
+                .collect::<Vec<_>>();
+            let next = boundary.iter().cloned().collect::<HashMap<_, _>>();
+            let start = boundary[0].0;
+            let mut ring = vec![start];
+            while let Some(vertex) = next.get(ring.last().unwrap()) {
+                if *vertex == start || ring.len() > boundary.len() {
+                    break;
+                }
+                ring.push(*vertex);
+            }
+            if next.len() == boundary.len() && ring.len() == boundary.len() {
+                polygons.push(ring);
+            }
+            else {
+                // The boundary of the group is not a simple cycle, so do not
+                // merge its facets.
+                polygons.extend(
+                    facets
+                        .iter()
+                        .map(|facet| self.facets[*facet].vertices.to_vec()),
+                );
+            }
+        }
+        // Retain only the positions of the vertices of the hull.
+        let mut remap = HashMap::new();
+        let mut positions = Vec::new();
+        let indices = polygons
+            .into_iter()
+            .map(|polygon| {
+                UnboundedPolygon(
+                    polygon
+                        .into_iter()
+                        .map(|point| {
+                            *remap.entry(point).or_insert_with(|| {
+                                positions.push(self.positions[point]);
+                                positions.len() - 1
+                            })
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+        ConvexHull { indices, positions }
+    }
+}
+
+/// Finds the point that maximizes a function.
+fn furthest<S, F>(positions: &[S], mut f: F) -> Option<(usize, Scalar<S>)>
+where
+    S: EuclideanSpace,
+    F: FnMut(S) -> Scalar<S>,
+{
+    positions
+        .iter()
+        .map(|position| f(*position))
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+}
+
+fn into_scalar<T, S>(value: T) -> Scalar<S>
+where
+    T: ToPrimitive,
+    S: EuclideanSpace,
+{
+    <Scalar<S> as NumCast>::from(value).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Point3;
+
+    use crate::graph::MeshGraph;
+    use crate::prelude::*;
+    use crate::primitive::hull::ConvexHull;
+
+    type E3 = Point3<f64>;
+
+    #[test]
+    fn cube_hull() {
+        let mut points = vec![];
+        for x in &[-0.5, 0.5] {
+            for y in &[-0.5, 0.5] {
+                for z in &[-0.5, 0.5] {
+                    points.push(E3::new(*x, *y, *z));
+                }
+            }
+        }
+        // Interior points.
+        points.push(E3::new(0.0, 0.0, 0.0));
+        points.push(E3::new(0.1, -0.3, 0.2));
+        points.push(E3::new(-0.4, 0.4, 0.45));
+
+        let hull = ConvexHull::from_points(points).unwrap();
+        assert_eq!(8, hull.positions().len());
+        assert_eq!(6, hull.indices().len());
+        assert!(hull.indices().iter().all(|polygon| polygon.arity() == 4));
+
+        let (indices, positions) = hull.into_raw_buffers();
+        let graph = MeshGraph::<E3>::from_raw_buffers(indices, positions).unwrap();
+        assert_eq!(8, graph.vertex_count());
+        assert_eq!(6, graph.face_count());
+        assert!(graph.arcs().all(|arc| !arc.is_boundary_arc()));
+        // The faces of the hull face outward.
+        assert!(graph.contains_point(E3::new(0.0, 0.0, 0.0)));
+        assert!(!graph.contains_point(E3::new(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn degenerate_hull() {
+        assert!(ConvexHull::from_points(vec![
+            E3::new(0.0, 0.0, 0.0),
+            E3::new(1.0, 0.0, 0.0),
+            E3::new(0.0, 1.0, 0.0),
+        ])
+        .is_none());
+        // Coplanar points.
+        assert!(ConvexHull::from_points(vec![
+            E3::new(0.0, 0.0, 0.0),
+            E3::new(1.0, 0.0, 0.0),
+            E3::new(0.0, 1.0, 0.0),
+            E3::new(1.0, 1.0, 0.0),
+        ])
+        .is_none());
+    }
+}
@@ -0,0 +1,458 @@
+//! Isosurfaces.
+//!
+//! This module provides [`Isosurface`], which extracts the surface on which a
+//! scalar field equals a given level via marching cubes. The field is sampled
+//! at the points of a regular grid and each cell of the grid is polygonized by
+//! tracing the surface across the faces of the cell. Each connected piece of
+//! the surface within a cell is traced as a ring, which is emitted as a fan of
+//! triangles or, optionally, as a single polygon. Faces of a cell on which the
+//! surface is ambiguous are resolved by the mean of the samples at their
+//! corners, which is the same for both of the cells that share such a face.
+//!
+//! Vertices are interpolated along the edges of the grid in the same way for
+//! all of the cells that share an edge, so coincident vertices can be merged
+//! by an [indexer][`index`] such as [`HashIndexer`].
+//!
+//! # Examples
+//!
+//! Extracting a sphere from an implicit function and collecting it into a
+//! graph:
+//!
+//! ```rust
+//! # extern crate decorum;
+//! # extern crate nalgebra;
+//! # extern crate plexus;
+//! #
+//! use decorum::N64;
+//! use nalgebra::Point3;
+//! use plexus::graph::MeshGraph;
+//! use plexus::index::HashIndexer;
+//! use plexus::prelude::*;
+//! use plexus::primitive::isosurface::Isosurface;
+//!
+//! type E3 = Point3<N64>;
+//!
+//! let radius: N64 = 0.7.into();
+//! let graph = Isosurface::new(
+//!     |position: E3| {
+//!         let (x, y, z) = (position.x, position.y, position.z);
+//!         (x * x) + (y * y) + (z * z) - (radius * radius)
+//!     },
+//!     E3::new((-1.0).into(), (-1.0).into(), (-1.0).into()),
+//!     E3::new(1.0.into(), 1.0.into(), 1.0.into()),
+//!     16,
+//! )
+//! .into_polygons()
+//! .collect_with_indexer::<MeshGraph<E3>, _>(HashIndexer::default())
+//! .unwrap();
+//! ```
+//!
+//! [`HashIndexer`]: crate::index::HashIndexer
+//! [`index`]: crate::index
+//! [`Isosurface`]: crate::primitive::isosurface::Isosurface
+
+use num::{NumCast, ToPrimitive, Zero};
+use smallvec::SmallVec;
+use std::cmp;
+use std::vec;
+use theon::space::{EuclideanSpace, FiniteDimensional, Scalar};
+use typenum::U3;
+
+use crate::geometry::component;
+use crate::primitive::{IntoPolygons, UnboundedPolygon};
+
+/// Edges of a cell as pairs of corners.
+///
+/// The index of a corner encodes its offset from the lowest corner of the cell
+/// along the $x$, $y$, and $z$ axes in its first, second, and third bits,
+/// respectively. The first corner of each edge is nearest to the lowest corner.
+const EDGES: [(usize, usize); 12] = [
+    (0, 1),
+    (2, 3),
+    (4, 5),
+    (6, 7),
+    (0, 2),
+    (1, 3),
+    (4, 6),
+    (5, 7),
+    (0, 4),
+    (1, 5),
+    (2, 6),
+    (3, 7),
+];
+
+/// Faces of a cell as rings of corners in counter-clockwise order when viewed
+/// from outside of the cell.
+const FACES: [[usize; 4]; 6] = [
+    [0, 4, 6, 2],
+    [1, 3, 7, 5],
+    [0, 1, 5, 4],
+    [2, 6, 7, 3],
+    [0, 2, 3, 1],
+    [4, 5, 7, 6],
+];
+
+/// Isosurface of a scalar field.
+///
+/// The field is sampled over a grid within the given bounds with the given
+/// number of cells along each axis. The surface separates the region in which
+/// the field is less than the level of the surface from the region in which it
+/// is not, and the normals of its polygons face the latter. For a signed
+/// distance function with the default level of zero, the normals face
+/// outward.
+///
+/// See the [module][`isosurface`] documentation.
+///
+/// [`isosurface`]: crate::primitive::isosurface
+pub struct Isosurface<S, F>
+where
+    S: EuclideanSpace,
+{
+    field: F,
+    lower: S,
+    upper: S,
+    resolution: [usize; 3],
+    level: Scalar<S>,
+    is_triangulated: bool,
+}
+
+impl<S, F> Isosurface<S, F>
+where
+    S: EuclideanSpace + FiniteDimensional<N = U3> + PartialEq,
+    F: FnMut(S) -> Scalar<S>,
+{
+    /// Creates an isosurface of a field sampled within the bounds given by
+    /// their lower and upper corners with the given number of cells along each
+    /// axis.
+    ///
+    /// The resolution is clamped to at least one cell.
+    pub fn new(field: F, lower: S, upper: S, resolution: usize) -> Self {
+        let resolution = cmp::max(1, resolution);
+        Isosurface {
+            field,
+            lower,
+            upper,
+            resolution: [resolution; 3],
+            level: Zero::zero(),
+            is_triangulated: true,
+        }
+    }
+
+    /// Sets the number of cells along each axis.
+    ///
+    /// Each resolution is clamped to at least one cell.
+    pub fn with_resolution(mut self, x: usize, y: usize, z: usize) -> Self {
+        self.resolution = [cmp::max(1, x), cmp::max(1, y), cmp::max(1, z)];
+        self
+    }
+
+    /// Sets the level of the field at which the surface is extracted.
+    pub fn with_level(mut self, level: Scalar<S>) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Sets whether or not pieces of the surface are emitted as triangles.
+    ///
+    /// By default, each connected piece of the surface within a cell is
+    /// emitted as a fan of triangles. Otherwise, each piece is emitted as a
+    /// single polygon, which may not be planar.
+    pub fn with_triangles(mut self, is_triangulated: bool) -> Self {
+        self.is_triangulated = is_triangulated;
+        self
+    }
+
+    fn polygonize(self) -> Vec<UnboundedPolygon<S>> {
+        let Isosurface {
+            mut field,
+            lower,
+            upper,
+            resolution,
+            level,
+            is_triangulated,
+        } = self;
+        let [nx, ny, nz] = resolution;
+        let index = |x: usize, y: usize, z: usize| x + ((nx + 1) * (y + ((ny + 1) * z)));
+        let coordinate = |axis: usize, n: usize| {
+            let (lower, upper) = (component(lower, axis), component(upper, axis));
+            let t = into_scalar::<_, S>(n) / into_scalar::<_, S>(resolution[axis]);
+            lower + ((upper - lower) * t)
+        };
+        let mut positions = Vec::with_capacity((nx + 1) * (ny + 1) * (nz + 1));
+        for z in 0..=nz {
+            for y in 0..=ny {
+                for x in 0..=nx {
+                    positions.push(S::from_xyz(
+                        coordinate(0, x),
+                        coordinate(1, y),
+                        coordinate(2, z),
+                    ));
+                }
+            }
+        }
+        let values = positions
+            .iter()
+            .map(|position| field(*position))
+            .collect::<Vec<_>>();
+
+        let mut polygons = Vec::new();
+        for z in 0..nz {
+            for y in 0..ny {
+                for x in 0..nx {
+                    let mut corners = [0usize; 8];
+                    for (corner, point) in corners.iter_mut().enumerate() {
+                        let (dx, dy, dz) = (corner & 1, (corner >> 1) & 1, corner >> 2);
+                        *point = index(x + dx, y + dy, z + dz);
+                    }
+                    let samples = [
+                        values[corners[0]],
+                        values[corners[1]],
+                        values[corners[2]],
+                        values[corners[3]],
+                        values[corners[4]],
+                        values[corners[5]],
+                        values[corners[6]],
+                        values[corners[7]],
+                    ];
+                    for ring in cell::<S>(&samples, level) {
+                        let mut polygon = SmallVec::<[S; 4]>::with_capacity(ring.len());
+                        for edge in ring {
+                            let (a, b) = EDGES[edge];
+                            let position = interpolate(
+                                (positions[corners[a]], samples[a]),
+                                (positions[corners[b]], samples[b]),
+                                level,
+                            );
+                            // Vertices of degenerate polygons may coincide if
+                            // the field equals the level at a corner.
+                            if polygon.last() != Some(&position) {
+                                polygon.push(position);
+                            }
+                        }
+                        while polygon.len() > 1 && polygon.first() == polygon.last() {
+                            polygon.pop();
+                        }
+                        if polygon.len() < 3 {
+                            continue;
+                        }
+                        if is_triangulated {
+                            polygons.extend((1..(polygon.len() - 1)).map(|index| {
+                                UnboundedPolygon(
+                                    [polygon[0], polygon[index], polygon[index + 1]]
+                                        .iter()
+                                        .cloned()
+                                        .collect(),
+                                )
+                            }));
+                        }
+                        else {
+                            polygons.push(UnboundedPolygon(polygon));
+                        }
+                    }
+                }
+            }
+        }
+        polygons
+    }
+}
+
+impl<S, F> IntoPolygons for Isosurface<S, F>
+where
+    S: EuclideanSpace + FiniteDimensional<N = U3> + PartialEq,
+    F: FnMut(S) -> Scalar<S>,
+{
+    type Output = vec::IntoIter<Self::Polygon>;
+    type Polygon = UnboundedPolygon<S>;
+
+    fn into_polygons(self) -> Self::Output {
+        self.polygonize().into_iter()
+    }
+}
+
+/// Polygonizes a cell.
+///
+/// Returns rings of the edges of the cell that are crossed by the surface.
+/// Each ring is a polygon in counter-clockwise order when viewed from the
+/// region in which the field is not less than the level.
+fn cell<S>(samples: &[Scalar<S>; 8], level: Scalar<S>) -> SmallVec<[SmallVec<[usize; 6]>; 2]>
+where
+    S: EuclideanSpace,
+{
+    let is_inside = |corner: usize| samples[corner] < level;
+    // Each crossed edge is entered by the surface on one face of the cell and
+    // exited on another. Map each edge to the next edge in its ring.
+    let mut next = [None; 12];
+    for face in FACES.iter() {
+        let mut crossings = SmallVec::<[(usize, bool); 4]>::new();
+        for (n, a) in face.iter().cloned().enumerate() {
+            let b = face[(n + 1) % 4];
+            if is_inside(a) != is_inside(b) {
+                // The crossing is an entry if the ring of the face moves from
+                // outside to inside of the surface.
+                crossings.push((edge(a, b), is_inside(b)));
+            }
+        }
+        match crossings.len() {
+            2 => {
+                let (entry, exit) = if crossings[0].1 {
+                    (crossings[0].0, crossings[1].0)
+                }
+                else {
+                    (crossings[1].0, crossings[0].0)
+                };
+                next[entry] = Some(exit);
+            }
+            4 => {
+                // The face is ambiguous. If its center is inside, then the
+                // surface separates its outside corners, and each exit is
+                // paired with the following entry. Otherwise, each exit is
+                // paired with the preceding entry.
+                let mean = face
+                    .iter()
+                    .fold(Scalar::<S>::zero(), |sum, corner| sum + samples[*corner])
+                    / into_scalar::<_, S>(4);
+                let offset = if mean < level { 1 } else { 3 };
+                for (n, (exit, is_entry)) in crossings.iter().cloned().enumerate() {
+                    if !is_entry {
+                        next[crossings[(n + offset) % 4].0] = Some(exit);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut rings = SmallVec::new();
+    let mut is_visited = [false; 12];
+    for (start, first) in next.iter().enumerate() {
+        if is_visited[start] || first.is_none() {
+            continue;
+        }
+        let mut ring = SmallVec::new();
+        let mut edge = start;
+        while !is_visited[edge] {
+            is_visited[edge] = true;
+            ring.push(edge);
+            edge = match next[edge] {
+                Some(edge) => edge,
+                None => break,
+            };
+        }
+        rings.push(ring);
+    }
+    rings
+}
+
+/// Gets the index of the edge between two corners of a cell.
+fn edge(a: usize, b: usize) -> usize {
+    let (a, b) = if a < b { (a, b) } else { (b, a) };
+    EDGES.iter().position(|edge| *edge == (a, b)).unwrap()
+}
+
+/// Interpolates the position at which the field equals the level along an
+/// edge.
+fn interpolate<S>(a: (S, Scalar<S>), b: (S, Scalar<S>), level: Scalar<S>) -> S
+where
+    S: EuclideanSpace,
+{
+    let ((a, va), (b, vb)) = (a, b);
+    a + ((b - a) * ((level - va) / (vb - va)))
+}
+
+fn into_scalar<T, S>(value: T) -> Scalar<S>
+where
+    T: ToPrimitive,
+    S: EuclideanSpace,
+{
+    <Scalar<S> as NumCast>::from(value).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use decorum::N64;
+    use nalgebra::Point3;
+
+    use crate::buffer::MeshBufferN;
+    use crate::graph::MeshGraph;
+    use crate::index::HashIndexer;
+    use crate::prelude::*;
+    use crate::primitive::isosurface::Isosurface;
+
+    type E3 = Point3<N64>;
+
+    fn sphere(radius: f64) -> Isosurface<E3, impl FnMut(E3) -> N64> {
+        let radius: N64 = radius.into();
+        Isosurface::new(
+            move |position: E3| {
+                let (x, y, z) = (position.x, position.y, position.z);
+                (x * x) + (y * y) + (z * z) - (radius * radius)
+            },
+            E3::new((-1.0).into(), (-1.0).into(), (-1.0).into()),
+            E3::new(1.0.into(), 1.0.into(), 1.0.into()),
+            12,
+        )
+    }
+
+    #[test]
+    fn sphere_isosurface() {
+        let graph = sphere(0.75)
+            .into_polygons()
+            .collect_with_indexer::<MeshGraph<E3>, _>(HashIndexer::default())
+            .unwrap();
+
+        assert!(graph.face_count() > 0);
+        assert!(graph.faces().all(|face| face.arity() == 3));
+        // The surface is closed.
+        assert!(graph.arcs().all(|arc| !arc.is_boundary_arc()));
+        for vertex in graph.vertices() {
+            let position = vertex.position();
+            let (x, y, z) = (position.x, position.y, position.z);
+            let distance = ((x * x) + (y * y) + (z * z)).into_inner().sqrt();
+            assert!((distance - 0.75).abs() < 0.05);
+        }
+        // The polygons face outward, so the signed volume is positive and
+        // approximates the volume of the sphere.
+        let volume = graph.volume().into_inner();
+        let expected = 4.0 / 3.0 * std::f64::consts::PI * 0.75f64.powi(3);
+        assert!((volume - expected).abs() < expected * 0.1);
+    }
+
+    #[test]
+    fn polygonal_sphere_isosurface() {
+        let triangles = sphere(0.75)
+            .into_polygons()
+            .collect_with_indexer::<MeshGraph<E3>, _>(HashIndexer::default())
+            .unwrap();
+        let graph = sphere(0.75)
+            .with_triangles(false)
+            .into_polygons()
+            .collect_with_indexer::<MeshGraph<E3>, _>(HashIndexer::default())
+            .unwrap();
+
+        assert!(graph.faces().any(|face| face.arity() > 3));
+        assert!(graph.face_count() < triangles.face_count());
+        assert_eq!(triangles.vertex_count(), graph.vertex_count());
+        assert!(graph.arcs().all(|arc| !arc.is_boundary_arc()));
+    }
+
+    #[test]
+    fn empty_isosurface() {
+        // The sphere does not intersect the bounds.
+        let surface = sphere(2.0);
+        assert_eq!(0, surface.into_polygons().count());
+    }
+
+    #[test]
+    fn isosurface_buffer() {
+        let buffer = sphere(0.6)
+            .into_polygons()
+            .collect_with_indexer::<MeshBufferN<usize, E3>, _>(HashIndexer::default())
+            .unwrap();
+        let graph = sphere(0.6)
+            .into_polygons()
+            .collect_with_indexer::<MeshGraph<E3>, _>(HashIndexer::default())
+            .unwrap();
+
+        assert_eq!(graph.vertex_count(), buffer.as_vertex_slice().len());
+        assert_eq!(graph.face_count(), buffer.as_index_slice().len());
+    }
+}
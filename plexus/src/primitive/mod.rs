@@ -83,6 +83,8 @@
 pub mod cube;
 pub mod decompose;
 pub mod generate;
+pub mod hull;
+pub mod isosurface;
 pub mod sphere;
 
 use arrayvec::{Array, ArrayVec};